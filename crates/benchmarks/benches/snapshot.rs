@@ -0,0 +1,48 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn snapshot(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snapshot");
+    let samples = [10, 100];
+    for sample in samples {
+        let mut s = String::new();
+        for i in 0..sample {
+            s += &format!("[header_no_{}]\n", i);
+            s += "entry = 42\n"
+        }
+        let doc = s.parse::<toml_edit::Document>().unwrap();
+
+        group.bench_with_input(BenchmarkId::new("clone", sample), &doc, |b, doc| {
+            b.iter(|| {
+                black_box(doc.clone());
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("snapshot", sample), &doc, |b, doc| {
+            b.iter(|| {
+                black_box(doc.snapshot());
+            })
+        });
+        let snapshot = doc.snapshot();
+        group.bench_with_input(
+            BenchmarkId::new("snapshot_clone", sample),
+            &snapshot,
+            |b, snapshot| {
+                b.iter(|| {
+                    black_box(snapshot.clone());
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("to_document", sample),
+            &snapshot,
+            |b, snapshot| {
+                b.iter(|| {
+                    black_box(snapshot.to_document());
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, snapshot);
+criterion_main!(benches);
@@ -1,6 +1,5 @@
-use std::error;
-use std::fmt;
-use std::str::{self, FromStr};
+use core::fmt;
+use core::str::{self, FromStr};
 
 #[cfg(feature = "serde")]
 use serde::{de, ser};
@@ -77,7 +76,11 @@ use serde::{de, ser};
 /// [Local Date-Time]: https://toml.io/en/v1.0.0#local-date-time
 /// [Local Date]: https://toml.io/en/v1.0.0#local-date
 /// [Local Time]: https://toml.io/en/v1.0.0#local-time
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+///
+/// The `PartialEq` and `Ord` implementations compare the fields as written, so
+/// `1979-05-27T07:32:00Z` and `1979-05-27T00:32:00-07:00` are not equal.  To
+/// compare the instants they represent, see [`Datetime::cmp_instant`].
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
 pub struct Datetime {
     /// Optional date.
     /// Required for: *Offset Date-Time*, *Local Date-Time*, *Local Date*.
@@ -124,7 +127,7 @@ pub const NAME: &str = "$__toml_private_Datetime";
 /// > ```
 ///
 /// [Local Date]: https://toml.io/en/v1.0.0#local-date
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
 pub struct Date {
     /// Year: four digits
     pub year: u16,
@@ -154,7 +157,7 @@ pub struct Date {
 /// > must be truncated, not rounded.
 ///
 /// [Local Time]: https://toml.io/en/v1.0.0#local-time
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
 pub struct Time {
     /// Hour: 0 to 23
     pub hour: u8,
@@ -168,7 +171,7 @@ pub struct Time {
 
 /// A parsed TOML time offset
 ///
-#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Copy, Clone, Debug)]
 pub enum Offset {
     /// > A suffix which, when applied to a time, denotes a UTC offset of 00:00;
     /// > often spoken "Zulu" from the ICAO phonetic alphabet representation of
@@ -204,6 +207,192 @@ impl From<Time> for Datetime {
     }
 }
 
+impl Datetime {
+    /// Compare the points in time represented by two datetimes
+    ///
+    /// Offset date-times are compared after normalizing to UTC.  Local
+    /// date-times, local dates, and local times only compare with their own
+    /// kind, returning `None` otherwise.
+    ///
+    /// ```rust
+    /// # use std::cmp::Ordering;
+    /// # use toml_datetime::Datetime;
+    /// let utc: Datetime = "1979-05-27T07:32:00Z".parse().unwrap();
+    /// let pdt: Datetime = "1979-05-27T00:32:00-07:00".parse().unwrap();
+    /// assert_eq!(utc.cmp_instant(&pdt), Some(Ordering::Equal));
+    ///
+    /// let local: Datetime = "1979-05-27T07:32:00".parse().unwrap();
+    /// assert_eq!(utc.cmp_instant(&local), None);
+    /// ```
+    pub fn cmp_instant(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        match (self, other) {
+            (
+                Datetime {
+                    date: Some(date),
+                    time: Some(time),
+                    offset: Some(offset),
+                },
+                Datetime {
+                    date: Some(other_date),
+                    time: Some(other_time),
+                    offset: Some(other_offset),
+                },
+            ) => {
+                let lhs = (utc_seconds(date, time, offset), time.nanosecond);
+                let rhs = (
+                    utc_seconds(other_date, other_time, other_offset),
+                    other_time.nanosecond,
+                );
+                Some(lhs.cmp(&rhs))
+            }
+            _ if self.kind() == other.kind() => Some(self.cmp(other)),
+            _ => None,
+        }
+    }
+
+    /// Whether two datetimes represent the same point in time
+    ///
+    /// See [`Datetime::cmp_instant`].
+    pub fn eq_instant(&self, other: &Self) -> bool {
+        self.cmp_instant(other) == Some(core::cmp::Ordering::Equal)
+    }
+
+    /// Convert a [`SystemTime`][std::time::SystemTime] into an offset date-time in UTC
+    ///
+    /// Only years 0000 through 9999 can be represented, as required by RFC 3339.
+    ///
+    /// ```rust
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// # use toml_datetime::Datetime;
+    /// let time = UNIX_EPOCH + Duration::new(296_638_320, 500_000_000);
+    /// let datetime = Datetime::from_system_time(time);
+    /// assert_eq!(datetime.to_string(), "1979-05-27T07:32:00.5Z");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_system_time(time: std::time::SystemTime) -> Self {
+        let (seconds, nanosecond) = match time.duration_since(std::time::UNIX_EPOCH) {
+            Ok(since) => (since.as_secs() as i64, since.subsec_nanos()),
+            Err(err) => {
+                let before = err.duration();
+                let seconds = -(before.as_secs() as i64);
+                match before.subsec_nanos() {
+                    0 => (seconds, 0),
+                    nanos => (seconds - 1, 1_000_000_000 - nanos),
+                }
+            }
+        };
+        utc_from_seconds(seconds, nanosecond)
+    }
+
+    /// Convert an offset date-time to the same point in time with a `Z` offset
+    ///
+    /// The date rolls over when the shift crosses midnight.  Local date-times, local dates, and
+    /// local times have no offset to normalize and return `None`.
+    ///
+    /// ```rust
+    /// # use toml_datetime::Datetime;
+    /// let pdt: Datetime = "1979-05-27T00:32:00-07:00".parse().unwrap();
+    /// assert_eq!(pdt.to_utc().unwrap().to_string(), "1979-05-27T07:32:00Z");
+    ///
+    /// let local: Datetime = "1979-05-27T07:32:00".parse().unwrap();
+    /// assert_eq!(local.to_utc(), None);
+    /// ```
+    pub fn to_utc(&self) -> Option<Self> {
+        match self {
+            Datetime {
+                date: Some(date),
+                time: Some(time),
+                offset: Some(offset),
+            } => Some(utc_from_seconds(
+                utc_seconds(date, time, offset),
+                time.nanosecond,
+            )),
+            _ => None,
+        }
+    }
+
+    /// The current time as an offset date-time in UTC
+    ///
+    /// See [`Datetime::from_system_time`].
+    #[cfg(feature = "std")]
+    pub fn now_utc() -> Self {
+        Self::from_system_time(std::time::SystemTime::now())
+    }
+
+    fn kind(&self) -> (bool, bool, bool) {
+        (
+            self.date.is_some(),
+            self.time.is_some(),
+            self.offset.is_some(),
+        )
+    }
+}
+
+/// Seconds since the Unix epoch, ignoring the sub-second part of `time`
+fn utc_seconds(date: &Date, time: &Time, offset: &Offset) -> i64 {
+    let offset_minutes = match *offset {
+        Offset::Z => 0,
+        Offset::Custom { minutes } => i64::from(minutes),
+    };
+    days_from_civil(date) * 86_400
+        + i64::from(time.hour) * 3_600
+        + i64::from(time.minute) * 60
+        + i64::from(time.second)
+        - offset_minutes * 60
+}
+
+/// The offset date-time in UTC `seconds` after the Unix epoch
+fn utc_from_seconds(seconds: i64, nanosecond: u32) -> Datetime {
+    let second_of_day = seconds.rem_euclid(86_400);
+    Datetime {
+        date: Some(civil_from_days(seconds.div_euclid(86_400))),
+        time: Some(Time {
+            hour: (second_of_day / 3_600) as u8,
+            minute: (second_of_day % 3_600 / 60) as u8,
+            second: (second_of_day % 60) as u8,
+            nanosecond,
+        }),
+        offset: Some(Offset::Z),
+    }
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar
+fn days_from_civil(date: &Date) -> i64 {
+    // Howard Hinnant's `days_from_civil`
+    let month = i64::from(date.month);
+    let year = i64::from(date.year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year =
+        (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(date.day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date `days` after 1970-01-01 in the proleptic Gregorian calendar
+fn civil_from_days(days: i64) -> Date {
+    // Howard Hinnant's `civil_from_days`
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    Date {
+        year: year as u16,
+        month: month as u8,
+        day: day as u8,
+    }
+}
+
 impl fmt::Display for Datetime {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(ref date) = self.date {
@@ -232,8 +421,13 @@ impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
         if self.nanosecond != 0 {
-            let s = format!("{:09}", self.nanosecond);
-            write!(f, ".{}", s.trim_end_matches('0'))?;
+            let mut nanosecond = self.nanosecond;
+            let mut width = 9;
+            while nanosecond % 10 == 0 {
+                nanosecond /= 10;
+                width -= 1;
+            }
+            write!(f, ".{:0width$}", nanosecond, width = width)?;
         }
         Ok(())
     }
@@ -471,6 +665,7 @@ impl ser::Serialize for Datetime {
     where
         S: ser::Serializer,
     {
+        use alloc::string::ToString;
         use serde::ser::SerializeStruct;
 
         let mut s = serializer.serialize_struct(NAME, 1)?;
@@ -589,4 +784,5 @@ impl fmt::Display for DatetimeParseError {
     }
 }
 
-impl error::Error for DatetimeParseError {}
+#[cfg(feature = "std")]
+impl std::error::Error for DatetimeParseError {}
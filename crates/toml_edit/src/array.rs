@@ -1,9 +1,10 @@
-use std::iter::FromIterator;
-use std::mem;
+use core::iter::FromIterator;
+use core::mem;
 
+use crate::prelude::*;
 use crate::repr::Decor;
 use crate::value::{DEFAULT_LEADING_VALUE_DECOR, DEFAULT_VALUE_DECOR};
-use crate::{Item, RawString, Value};
+use crate::{InlineTable, Item, RawString, Value};
 
 /// Type representing a TOML array,
 /// payload of the `Value::Array` variant's value
@@ -15,17 +16,76 @@ pub struct Array {
     trailing_comma: bool,
     // prefix before `[` and suffix after `]`
     decor: Decor,
-    pub(crate) span: Option<std::ops::Range<usize>>,
+    pub(crate) span: Option<core::ops::Range<usize>>,
+    enforce_homogeneous: bool,
     // always Vec<Item::Value>
     pub(crate) values: Vec<Item>,
 }
 
-/// An owned iterator type over `Table`'s key/value pairs.
-pub type ArrayIntoIter = Box<dyn Iterator<Item = Value>>;
-/// An iterator type over `Array`'s values.
-pub type ArrayIter<'a> = Box<dyn Iterator<Item = &'a Value> + 'a>;
-/// An iterator type over `Array`'s values.
-pub type ArrayIterMut<'a> = Box<dyn Iterator<Item = &'a mut Value> + 'a>;
+macro_rules! array_iter {
+    ($(#[$attr:meta])* $name:ident$(<$lt:lifetime>)?, $inner:ty, $item:ty, $as_value:expr) => {
+        $(#[$attr])*
+        pub struct $name$(<$lt>)? {
+            iter: $inner,
+            // placeholder elements are skipped, so this may be less than `iter.len()`
+            len: usize,
+        }
+
+        impl$(<$lt>)? Iterator for $name$(<$lt>)? {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let value = self.iter.by_ref().find_map($as_value)?;
+                self.len -= 1;
+                Some(value)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl$(<$lt>)? DoubleEndedIterator for $name$(<$lt>)? {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let value = self.iter.by_ref().rev().find_map($as_value)?;
+                self.len -= 1;
+                Some(value)
+            }
+        }
+
+        impl$(<$lt>)? ExactSizeIterator for $name$(<$lt>)? {}
+
+        impl$(<$lt>)? core::iter::FusedIterator for $name$(<$lt>)? {}
+    };
+}
+
+array_iter!(
+    /// An owned iterator type over `Array`'s values.
+    #[derive(Debug, Clone)]
+    ArrayIntoIter,
+    alloc::vec::IntoIter<Item>,
+    Value,
+    |item| match item {
+        Item::Value(v) => Some(v),
+        _ => None,
+    }
+);
+array_iter!(
+    /// An iterator type over `Array`'s values.
+    #[derive(Debug, Clone)]
+    ArrayIter<'a>,
+    core::slice::Iter<'a, Item>,
+    &'a Value,
+    Item::as_value
+);
+array_iter!(
+    /// An iterator type over `Array`'s values.
+    #[derive(Debug)]
+    ArrayIterMut<'a>,
+    core::slice::IterMut<'a, Item>,
+    &'a mut Value,
+    Item::as_value_mut
+);
 
 /// Constructors
 ///
@@ -42,6 +102,61 @@ impl Array {
         Default::default()
     }
 
+    /// Create an `Array` of integers
+    pub fn of_integers<I: IntoIterator<Item = i64>>(values: I) -> Self {
+        values.into_iter().map(Value::integer).collect()
+    }
+
+    /// Create an `Array` of floats
+    pub fn of_floats<I: IntoIterator<Item = f64>>(values: I) -> Self {
+        values.into_iter().map(Value::float).collect()
+    }
+
+    /// Create an `Array` of strings
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let arr = toml_edit::Array::of_strings(["a", "b"]);
+    /// assert_eq!(arr.to_string(), r#"["a", "b"]"#);
+    /// # }
+    /// ```
+    pub fn of_strings<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        values.into_iter().map(|s| Value::from(s.into())).collect()
+    }
+
+    /// Create an `Array` of inline tables
+    ///
+    /// Each table's own decor is dropped so the elements are separated like newly pushed values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// use toml_edit::{Array, InlineTable};
+    ///
+    /// let mut a = InlineTable::new();
+    /// a.insert("name", "a".into());
+    /// let arr = Array::of_inline_tables([a, InlineTable::new()]);
+    /// assert_eq!(arr.len(), 2);
+    /// # }
+    /// ```
+    pub fn of_inline_tables<I: IntoIterator<Item = InlineTable>>(values: I) -> Self {
+        values
+            .into_iter()
+            .map(|table| {
+                let mut value = Value::InlineTable(table);
+                value.decor_mut().clear();
+                value
+            })
+            .collect()
+    }
+
     pub(crate) fn with_vec(values: Vec<Item>) -> Self {
         Self {
             values,
@@ -68,11 +183,15 @@ impl Array {
     }
 
     /// Set whitespace after last element
+    ///
+    /// This may include comments, like the one before `]` in `[\n  1,\n  # last\n]`.
     pub fn set_trailing(&mut self, trailing: impl Into<RawString>) {
         self.trailing = trailing.into();
     }
 
     /// Whitespace after last element
+    ///
+    /// When the array has a trailing comma, this includes comments before `]`.
     pub fn trailing(&self) -> &RawString {
         &self.trailing
     }
@@ -88,7 +207,7 @@ impl Array {
     }
 
     /// Returns the location within the original document
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         self.span.clone()
     }
 
@@ -104,13 +223,29 @@ impl Array {
 
 impl Array {
     /// Returns an iterator over all values.
+    ///
+    /// The iterator is double-ended and knows its length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let arr = toml_edit::Array::from_iter([1, 2, 3]);
+    /// assert_eq!(arr.iter().len(), 3);
+    /// assert_eq!(arr.iter().rev().nth(0).and_then(|v| v.as_integer()), Some(3));
+    /// ```
     pub fn iter(&self) -> ArrayIter<'_> {
-        Box::new(self.values.iter().filter_map(Item::as_value))
+        ArrayIter {
+            len: self.value_count(),
+            iter: self.values.iter(),
+        }
     }
 
     /// Returns an iterator over all values.
     pub fn iter_mut(&mut self) -> ArrayIterMut<'_> {
-        Box::new(self.values.iter_mut().filter_map(Item::as_value_mut))
+        ArrayIterMut {
+            len: self.value_count(),
+            iter: self.values.iter_mut(),
+        }
     }
 
     /// Returns the length of the underlying Vec.
@@ -163,6 +298,26 @@ impl Array {
         self.values.get_mut(index).and_then(Item::as_value_mut)
     }
 
+    /// Returns a reference to the first value, or `None` if the array is empty.
+    pub fn first(&self) -> Option<&Value> {
+        self.values.first().and_then(Item::as_value)
+    }
+
+    /// Returns a mutable reference to the first value, or `None` if the array is empty.
+    pub fn first_mut(&mut self) -> Option<&mut Value> {
+        self.values.first_mut().and_then(Item::as_value_mut)
+    }
+
+    /// Returns a reference to the last value, or `None` if the array is empty.
+    pub fn last(&self) -> Option<&Value> {
+        self.values.last().and_then(Item::as_value)
+    }
+
+    /// Returns a mutable reference to the last value, or `None` if the array is empty.
+    pub fn last_mut(&mut self) -> Option<&mut Value> {
+        self.values.last_mut().and_then(Item::as_value_mut)
+    }
+
     /// Appends a new value to the end of the array, applying default formatting to it.
     ///
     /// # Examples
@@ -172,12 +327,48 @@ impl Array {
     /// arr.push(1);
     /// arr.push("foo");
     /// ```
+    ///
+    /// Any value is accepted, whatever [`Array::enforce_homogeneous`], see [`Array::try_push`].
     pub fn push<V: Into<Value>>(&mut self, v: V) {
         self.value_op(v.into(), true, |items, value| {
             items.push(Item::Value(value))
         })
     }
 
+    /// Appends a new value to the end of the array, returning the array for further chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let arr = toml_edit::Array::new().with(1).with(2);
+    /// assert_eq!(arr.to_string(), "[1, 2]");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with<V: Into<Value>>(mut self, v: V) -> Self {
+        self.push(v);
+        self
+    }
+
+    /// Appends a new value to the end of the array, applying default formatting to it.
+    ///
+    /// When [`Array::enforce_homogeneous`] is set, a value whose type differs from the existing
+    /// values is handed back instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut arr = toml_edit::Array::from_iter([1, 2]);
+    /// arr.set_enforce_homogeneous(true);
+    /// assert!(arr.try_push(3).is_ok());
+    /// assert_eq!(arr.try_push("foo").unwrap_err().as_str(), Some("foo"));
+    /// assert_eq!(arr.len(), 3);
+    /// ```
+    pub fn try_push<V: Into<Value>>(&mut self, v: V) -> Result<(), Value> {
+        self.homogeneous_value_op(v.into(), |items, value| items.push(Item::Value(value)))
+    }
+
     /// Appends a new, already formatted value to the end of the array.
     ///
     /// # Examples
@@ -193,9 +384,40 @@ impl Array {
         self.values.push(Item::Value(v));
     }
 
+    /// Moves all the values of `other` to the end of the array, leaving `other` empty.
+    ///
+    /// The values keep their formatting, except that the first value moved after existing values
+    /// is separated from them by a space.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let mut arr = toml_edit::Array::from_iter([1, 2]);
+    /// let mut other = toml_edit::Array::from_iter([3, 4]);
+    /// arr.append(&mut other);
+    /// assert_eq!(arr.to_string(), "[1, 2, 3, 4]");
+    /// assert!(other.is_empty());
+    /// # }
+    /// ```
+    pub fn append(&mut self, other: &mut Array) {
+        let mut values = mem::take(&mut other.values);
+        if !self.is_empty() {
+            if let Some(first) = values.first_mut().and_then(Item::as_value_mut) {
+                let prefix = first.decor().prefix().and_then(RawString::as_str);
+                if prefix == Some("") {
+                    first.decor_mut().set_prefix(DEFAULT_VALUE_DECOR.0);
+                }
+            }
+        }
+        self.values.append(&mut values);
+    }
+
     /// Inserts an element at the given position within the array, applying default formatting to
     /// it and shifting all values after it to the right.
     ///
+    /// Any value is accepted, whatever [`Array::enforce_homogeneous`], see [`Array::try_insert`].
+    ///
     /// # Panics
     ///
     /// Panics if `index > len`.
@@ -215,9 +437,42 @@ impl Array {
         })
     }
 
+    /// Inserts an element at the given position within the array, applying default formatting to
+    /// it and shifting all values after it to the right.
+    ///
+    /// When [`Array::enforce_homogeneous`] is set, a value whose type differs from the existing
+    /// values is handed back instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    pub fn try_insert<V: Into<Value>>(&mut self, index: usize, v: V) -> Result<(), Value> {
+        self.homogeneous_value_op(v.into(), |items, value| {
+            items.insert(index, Item::Value(value))
+        })
+    }
+
+    /// Set whether [`Array::try_push`] and [`Array::try_insert`] reject values of another type
+    /// than the existing ones
+    ///
+    /// Off by default, as TOML 1.0 allows mixed arrays.  Any value may be added to an empty array,
+    /// and the other ways of adding values, like [`Array::push`], never check.
+    pub fn set_enforce_homogeneous(&mut self, yes: bool) {
+        self.enforce_homogeneous = yes;
+    }
+
+    /// Whether [`Array::try_push`] and [`Array::try_insert`] reject values of another type than
+    /// the existing ones
+    pub fn enforce_homogeneous(&self) -> bool {
+        self.enforce_homogeneous
+    }
+
     /// Inserts an already formatted value at the given position within the array, shifting all
     /// values after it to the right.
     ///
+    /// The value's decor is used verbatim, so neighbouring separators may need adjusting, see
+    /// [`Array::fix_separators`].
+    ///
     /// # Panics
     ///
     /// Panics if `index > len`.
@@ -238,6 +493,48 @@ impl Array {
         self.values.insert(index, Item::Value(v))
     }
 
+    /// Normalizes the spacing around commas, keeping line breaks and comments.
+    ///
+    /// Values whose decor is only spaces get the default decor: nothing around the first value
+    /// and a single space before the others.  Unlike [`Array::fmt`], a multi-line layout is kept.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let mut arr = toml_edit::Array::from_iter([1, 2]);
+    /// arr.insert_formatted(0, toml_edit::Value::from(0).decorated("  ", " "));
+    /// assert_eq!(arr.to_string(), "[  0 , 1, 2]");
+    /// arr.fix_separators();
+    /// assert_eq!(arr.to_string(), "[0, 1, 2]");
+    /// # }
+    /// ```
+    pub fn fix_separators(&mut self) {
+        let is_inline_space = |raw: Option<&RawString>| {
+            raw.and_then(RawString::as_str)
+                .map_or(true, |s| s.bytes().all(|b| b == b' ' || b == b'\t'))
+        };
+        for (i, value) in self
+            .values
+            .iter_mut()
+            .filter_map(Item::as_value_mut)
+            .enumerate()
+        {
+            let default = if i == 0 {
+                DEFAULT_LEADING_VALUE_DECOR
+            } else {
+                DEFAULT_VALUE_DECOR
+            };
+            let decor = value.decor_mut();
+            if is_inline_space(decor.prefix()) {
+                decor.set_prefix(default.0);
+            }
+            if is_inline_space(decor.suffix()) {
+                decor.set_suffix(default.1);
+            }
+        }
+    }
+
     /// Replaces the element at the given position within the array, preserving existing formatting.
     ///
     /// # Panics
@@ -309,6 +606,81 @@ impl Array {
         }
     }
 
+    /// Shortens the array to `len` values, dropping the rest.
+    ///
+    /// Whatever the old last value had before the `]`, like a newline, moves to the new last value,
+    /// or to [`Array::trailing`] if no value is left.  Does nothing if `len` is at least the
+    /// current length.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let mut arr = toml_edit::Array::from_iter([1, 2, 3, 4]);
+    /// arr.truncate(2);
+    /// assert_eq!(arr.to_string(), "[1, 2]");
+    /// # }
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        let closing = self.take_closing();
+        self.values.truncate(len);
+        self.restore_closing(closing);
+    }
+
+    /// Resizes the array to `new_len` values, truncating it or pushing values returned by `f`.
+    ///
+    /// Whatever the old last value had before the `]` moves to the new last value, see
+    /// [`Array::truncate`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let mut arr = toml_edit::Array::from_iter([1]);
+    /// arr.resize_with(3, || 0.into());
+    /// assert_eq!(arr.to_string(), "[1, 0, 0]");
+    /// # }
+    /// ```
+    pub fn resize_with<F>(&mut self, new_len: usize, mut f: F)
+    where
+        F: FnMut() -> Value,
+    {
+        if new_len <= self.len() {
+            self.truncate(new_len);
+            return;
+        }
+        let closing = self.take_closing();
+        while self.len() < new_len {
+            self.push(f());
+        }
+        self.restore_closing(closing);
+    }
+
+    /// Creates a new array by applying `f` to each value, with default formatting.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let arr = toml_edit::Array::from_iter([1, 2, 3]);
+    /// let doubled = arr.map(|v| (v.as_integer().unwrap() * 2).into());
+    /// assert_eq!(doubled.to_string(), "[2, 4, 6]");
+    /// # }
+    /// ```
+    pub fn map<F>(&self, mut f: F) -> Array
+    where
+        F: FnMut(&Value) -> Value,
+    {
+        let mut array = Array::new();
+        for value in self.iter() {
+            array.push(f(value));
+        }
+        array
+    }
+
     /// Retains only the values specified by the `keep` predicate.
     ///
     /// In other words, remove all values for which `keep(&value)` returns `false`.
@@ -323,6 +695,35 @@ impl Array {
             .retain(|item| item.as_value().map(&mut keep).unwrap_or(false));
     }
 
+    /// Removes later duplicates, keeping the first occurrence of each value.
+    ///
+    /// Values are compared with [`Value`]'s `PartialEq` and `Hash`, which ignore formatting and
+    /// decor, so `"a"` and `'a'` are considered equal.  This takes linear time, using a hash set
+    /// of the values seen so far.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let mut arr = toml_edit::Array::from_iter(["a", "b", "a"]);
+    /// arr.dedup();
+    /// assert_eq!(arr.to_string(), r#"["a", "b"]"#);
+    /// # }
+    /// ```
+    pub fn dedup(&mut self) {
+        let mut seen = indexmap::IndexSet::with_capacity_and_hasher(
+            self.values.len(),
+            crate::table::RandomState::default(),
+        );
+        let keep = self
+            .values
+            .iter()
+            .map(|item| item.as_value().map_or(false, |value| seen.insert(value)))
+            .collect::<Vec<_>>();
+        let mut keep = keep.into_iter();
+        self.values.retain(|_| keep.next().unwrap_or(false));
+    }
+
     /// Sorts the slice with a comparator function.
     ///
     /// This sort is stable (i.e., does not reorder equal elements) and *O*(*n* \* log(*n*)) worst-case.
@@ -339,15 +740,15 @@ impl Array {
     #[inline]
     pub fn sort_by<F>(&mut self, mut compare: F)
     where
-        F: FnMut(&Value, &Value) -> std::cmp::Ordering,
+        F: FnMut(&Value, &Value) -> core::cmp::Ordering,
     {
         self.values.sort_by(move |lhs, rhs| {
             let lhs = lhs.as_value();
             let rhs = rhs.as_value();
             match (lhs, rhs) {
-                (None, None) => std::cmp::Ordering::Equal,
-                (Some(_), None) => std::cmp::Ordering::Greater,
-                (None, Some(_)) => std::cmp::Ordering::Less,
+                (None, None) => core::cmp::Ordering::Equal,
+                (Some(_), None) => core::cmp::Ordering::Greater,
+                (None, Some(_)) => core::cmp::Ordering::Less,
                 (Some(lhs), Some(rhs)) => compare(lhs, rhs),
             }
         })
@@ -373,6 +774,35 @@ impl Array {
         });
     }
 
+    /// Removes the suffix of the last value, which sits right before the `]`
+    fn value_count(&self) -> usize {
+        self.values.iter().filter(|item| item.is_value()).count()
+    }
+
+    fn take_closing(&mut self) -> Option<RawString> {
+        let last = self.last_mut()?;
+        let closing = last.decor().suffix().cloned().unwrap_or_default();
+        last.decor_mut().set_suffix("");
+        Some(closing)
+    }
+
+    fn restore_closing(&mut self, closing: Option<RawString>) {
+        let closing = match closing {
+            Some(closing) => closing,
+            None => return,
+        };
+        match self.last_mut() {
+            Some(last) => {
+                last.decor_mut().set_suffix(closing);
+            }
+            None => {
+                if closing.as_str() != Some("") {
+                    self.set_trailing(closing);
+                }
+            }
+        }
+    }
+
     fn value_op<T>(
         &mut self,
         v: Value,
@@ -387,11 +817,26 @@ impl Array {
         }
         op(&mut self.values, value)
     }
+
+    /// [`Array::value_op`], unless [`Array::enforce_homogeneous`] rejects `v`
+    fn homogeneous_value_op(
+        &mut self,
+        v: Value,
+        op: impl FnOnce(&mut Vec<Item>, Value),
+    ) -> Result<(), Value> {
+        let accepts = !self.enforce_homogeneous
+            || self.first().map_or(true, |first| first.kind() == v.kind());
+        if !accepts {
+            return Err(v);
+        }
+        self.value_op(v, true, op);
+        Ok(())
+    }
 }
 
 #[cfg(feature = "display")]
-impl std::fmt::Display for Array {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Array {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, None, ("", ""))
     }
 }
@@ -422,12 +867,10 @@ impl IntoIterator for Array {
     type IntoIter = ArrayIntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        Box::new(
-            self.values
-                .into_iter()
-                .filter(|v| v.is_value())
-                .map(|v| v.into_value().unwrap()),
-        )
+        ArrayIntoIter {
+            len: self.value_count(),
+            iter: self.values.into_iter(),
+        }
     }
 }
 
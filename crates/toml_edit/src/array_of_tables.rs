@@ -1,12 +1,14 @@
-use std::iter::FromIterator;
+use core::iter::FromIterator;
 
+use crate::prelude::*;
+use crate::repr::Decor;
 use crate::{Array, Item, Table};
 
 /// Type representing a TOML array of tables
 #[derive(Clone, Debug, Default)]
 pub struct ArrayOfTables {
     // Always Vec<Item::Table>, just `Item` to make `Index` work
-    pub(crate) span: Option<std::ops::Range<usize>>,
+    pub(crate) span: Option<core::ops::Range<usize>>,
     pub(crate) values: Vec<Item>,
 }
 
@@ -32,8 +34,32 @@ impl ArrayOfTables {
         a
     }
 
+    /// Returns the whitespace and comments around the `[[header]]` of the table at `index`
+    ///
+    /// Comments on the lines before a header belong to its prefix, so they move with the table.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let doc = "[[a]]\nx = 1\n# second\n[[a]]\nx = 2\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let a = doc["a"].as_array_of_tables().unwrap();
+    /// let prefix = a.header_decor(1).unwrap().prefix().unwrap();
+    /// assert_eq!(prefix.as_str(), Some("# second\n"));
+    /// # }
+    /// ```
+    pub fn header_decor(&self, index: usize) -> Option<&Decor> {
+        self.get(index).map(Table::decor)
+    }
+
+    /// Returns the whitespace and comments around the `[[header]]` of the table at `index`
+    pub fn header_decor_mut(&mut self, index: usize) -> Option<&mut Decor> {
+        self.get_mut(index).map(Table::decor_mut)
+    }
+
     /// Returns the location within the original document
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         self.span.clone()
     }
 
@@ -159,8 +185,8 @@ impl<'s> IntoIterator for &'s ArrayOfTables {
 }
 
 #[cfg(feature = "display")]
-impl std::fmt::Display for ArrayOfTables {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for ArrayOfTables {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         // HACK: Without the header, we don't really have a proper way of printing this
         self.clone().into_array().fmt(f)
     }
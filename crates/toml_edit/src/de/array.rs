@@ -3,11 +3,21 @@ use crate::de::Error;
 pub(crate) struct ArrayDeserializer {
     input: Vec<crate::Item>,
     span: Option<std::ops::Range<usize>>,
+    datetime_strings: bool,
 }
 
 impl ArrayDeserializer {
     pub(crate) fn new(input: Vec<crate::Item>, span: Option<std::ops::Range<usize>>) -> Self {
-        Self { input, span }
+        Self {
+            input,
+            span,
+            datetime_strings: false,
+        }
+    }
+
+    pub(crate) fn with_datetime_strings(mut self, yes: bool) -> Self {
+        self.datetime_strings = yes;
+        self
     }
 }
 
@@ -20,7 +30,7 @@ impl<'de> serde::Deserializer<'de> for ArrayDeserializer {
     where
         V: serde::de::Visitor<'de>,
     {
-        visitor.visit_seq(ArraySeqAccess::new(self.input))
+        visitor.visit_seq(ArraySeqAccess::new(self.input, self.datetime_strings))
     }
 
     fn deserialize_struct<V>(
@@ -70,12 +80,14 @@ impl crate::ArrayOfTables {
 
 pub(crate) struct ArraySeqAccess {
     iter: std::vec::IntoIter<crate::Item>,
+    datetime_strings: bool,
 }
 
 impl ArraySeqAccess {
-    pub(crate) fn new(input: Vec<crate::Item>) -> Self {
+    pub(crate) fn new(input: Vec<crate::Item>, datetime_strings: bool) -> Self {
         Self {
             iter: input.into_iter(),
+            datetime_strings,
         }
     }
 }
@@ -89,7 +101,10 @@ impl<'de> serde::de::SeqAccess<'de> for ArraySeqAccess {
     {
         match self.iter.next() {
             Some(v) => seed
-                .deserialize(crate::de::ValueDeserializer::new(v))
+                .deserialize(
+                    crate::de::ValueDeserializer::new(v)
+                        .with_datetime_strings(self.datetime_strings),
+                )
                 .map(Some),
             None => Ok(None),
         }
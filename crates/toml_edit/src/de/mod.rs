@@ -25,6 +25,7 @@ pub use value::ValueDeserializer;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Error {
     inner: crate::TomlError,
+    utf8_offset: Option<usize>,
 }
 
 impl Error {
@@ -34,6 +35,7 @@ impl Error {
     {
         Error {
             inner: crate::TomlError::custom(msg.to_string(), span),
+            utf8_offset: None,
         }
     }
 
@@ -52,6 +54,12 @@ impl Error {
         self.inner.span()
     }
 
+    /// The byte offset of the first invalid UTF-8 sequence, if the input to `from_slice` was
+    /// not valid UTF-8
+    pub fn utf8_offset(&self) -> Option<usize> {
+        self.utf8_offset
+    }
+
     pub(crate) fn set_span(&mut self, span: Option<std::ops::Range<usize>>) {
         self.inner.set_span(span);
     }
@@ -74,7 +82,10 @@ impl std::fmt::Display for Error {
 
 impl From<crate::TomlError> for Error {
     fn from(e: crate::TomlError) -> Error {
-        Self { inner: e }
+        Self {
+            inner: e,
+            utf8_offset: None,
+        }
     }
 }
 
@@ -97,12 +108,21 @@ where
 }
 
 /// Convert a value into `T`.
+///
+/// Invalid UTF-8 is reported with [`Error::utf8_offset`], and [`Error::span`] covering the first
+/// invalid sequence.
 #[cfg(feature = "parse")]
 pub fn from_slice<T>(s: &'_ [u8]) -> Result<T, Error>
 where
     T: DeserializeOwned,
 {
-    let s = std::str::from_utf8(s).map_err(|e| Error::custom(e, None))?;
+    let s = std::str::from_utf8(s).map_err(|e| {
+        let offset = e.valid_up_to();
+        let end = e.error_len().map_or(s.len(), |len| offset + len);
+        let mut err = Error::custom(e, Some(offset..end));
+        err.utf8_offset = Some(offset);
+        err
+    })?;
     from_str(s)
 }
 
@@ -290,3 +310,49 @@ pub(crate) fn validate_struct_keys(
         ))
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "parse")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_parsed_table() {
+        use serde::de::IntoDeserializer;
+        use serde::Deserialize as _;
+
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Server {
+            host: String,
+            ports: Vec<u16>,
+            started: String,
+            limits: std::collections::BTreeMap<String, i64>,
+        }
+
+        let doc = r#"
+[server]
+host = "localhost"
+ports = [80, 443]
+started = 1979-05-27T07:32:00Z
+limits = { conn = 10 }
+"#
+        .parse::<crate::Document>()
+        .unwrap();
+        // Through the trait, as `Table` has a crate-private method of the same name
+        let table = doc["server"].as_table().unwrap().clone();
+        let server = Server::deserialize(IntoDeserializer::into_deserializer(table)).unwrap();
+        assert_eq!(
+            server,
+            Server {
+                host: "localhost".to_owned(),
+                ports: vec![80, 443],
+                started: "1979-05-27T07:32:00Z".to_owned(),
+                limits: [("conn".to_owned(), 10)].into_iter().collect(),
+            }
+        );
+
+        let ports = doc["server"]["ports"].as_value().unwrap().clone();
+        let ports = Vec::<u16>::deserialize(ports.into_deserializer()).unwrap();
+        assert_eq!(ports, [80, 443]);
+    }
+}
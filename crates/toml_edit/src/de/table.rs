@@ -5,6 +5,14 @@ use crate::de::Error;
 pub(crate) struct TableDeserializer {
     span: Option<std::ops::Range<usize>>,
     items: crate::table::KeyValuePairs,
+    datetime_strings: bool,
+}
+
+impl TableDeserializer {
+    pub(crate) fn with_datetime_strings(mut self, yes: bool) -> Self {
+        self.datetime_strings = yes;
+        self
+    }
 }
 
 // Note: this is wrapped by `Deserializer` and `ValueDeserializer` and any trait methods
@@ -102,6 +110,7 @@ impl crate::Table {
         TableDeserializer {
             span: self.span(),
             items: self.items,
+            datetime_strings: false,
         }
     }
 }
@@ -111,6 +120,7 @@ impl crate::InlineTable {
         TableDeserializer {
             span: self.span(),
             items: self.items,
+            datetime_strings: false,
         }
     }
 }
@@ -119,6 +129,7 @@ pub(crate) struct TableMapAccess {
     iter: indexmap::map::IntoIter<crate::InternalString, crate::table::TableKeyValue>,
     span: Option<std::ops::Range<usize>>,
     value: Option<(crate::InternalString, crate::Item)>,
+    datetime_strings: bool,
 }
 
 impl TableMapAccess {
@@ -127,6 +138,7 @@ impl TableMapAccess {
             iter: input.items.into_iter(),
             span: input.span,
             value: None,
+            datetime_strings: input.datetime_strings,
         }
     }
 }
@@ -163,14 +175,17 @@ impl<'de> serde::de::MapAccess<'de> for TableMapAccess {
         match self.value.take() {
             Some((k, v)) => {
                 let span = v.span();
-                seed.deserialize(crate::de::ValueDeserializer::new(v))
-                    .map_err(|mut e: Self::Error| {
-                        if e.span().is_none() {
-                            e.set_span(span);
-                        }
-                        e.add_key(k.as_str().to_owned());
-                        e
-                    })
+                seed.deserialize(
+                    crate::de::ValueDeserializer::new(v)
+                        .with_datetime_strings(self.datetime_strings),
+                )
+                .map_err(|mut e: Self::Error| {
+                    if e.span().is_none() {
+                        e.set_span(span);
+                    }
+                    e.add_key(k.as_str().to_owned());
+                    e
+                })
             }
             None => {
                 panic!("no more values in next_value_seed, internal error in ValueDeserializer")
@@ -206,7 +221,8 @@ impl<'de> serde::de::EnumAccess<'de> for TableMapAccess {
                 e
             })?;
 
-        let variant = super::TableEnumDeserializer::new(value.value);
+        let variant = super::TableEnumDeserializer::new(value.value)
+            .with_datetime_strings(self.datetime_strings);
 
         Ok((val, variant))
     }
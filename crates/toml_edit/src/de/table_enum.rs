@@ -3,11 +3,20 @@ use crate::de::Error;
 /// Deserializes table values into enum variants.
 pub(crate) struct TableEnumDeserializer {
     value: crate::Item,
+    datetime_strings: bool,
 }
 
 impl TableEnumDeserializer {
     pub(crate) fn new(value: crate::Item) -> Self {
-        TableEnumDeserializer { value }
+        TableEnumDeserializer {
+            value,
+            datetime_strings: false,
+        }
+    }
+
+    pub(crate) fn with_datetime_strings(mut self, yes: bool) -> Self {
+        self.datetime_strings = yes;
+        self
     }
 }
 
@@ -55,7 +64,9 @@ impl<'de> serde::de::VariantAccess<'de> for TableEnumDeserializer {
     where
         T: serde::de::DeserializeSeed<'de>,
     {
-        seed.deserialize(super::ValueDeserializer::new(self.value))
+        seed.deserialize(
+            super::ValueDeserializer::new(self.value).with_datetime_strings(self.datetime_strings),
+        )
     }
 
     fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
@@ -69,7 +80,8 @@ impl<'de> serde::de::VariantAccess<'de> for TableEnumDeserializer {
 
                 if tuple_values.len() == len {
                     serde::de::Deserializer::deserialize_seq(
-                        super::ArrayDeserializer::new(tuple_values, values_span),
+                        super::ArrayDeserializer::new(tuple_values, values_span)
+                            .with_datetime_strings(self.datetime_strings),
                         visitor,
                     )
                 } else {
@@ -85,7 +97,8 @@ impl<'de> serde::de::VariantAccess<'de> for TableEnumDeserializer {
 
                 if tuple_values.len() == len {
                     serde::de::Deserializer::deserialize_seq(
-                        super::ArrayDeserializer::new(tuple_values, values_span),
+                        super::ArrayDeserializer::new(tuple_values, values_span)
+                            .with_datetime_strings(self.datetime_strings),
                         visitor,
                     )
                 } else {
@@ -119,7 +132,8 @@ impl<'de> serde::de::VariantAccess<'de> for TableEnumDeserializer {
 
                 if tuple_values.len() == len {
                     serde::de::Deserializer::deserialize_seq(
-                        super::ArrayDeserializer::new(tuple_values, values_span),
+                        super::ArrayDeserializer::new(tuple_values, values_span)
+                            .with_datetime_strings(self.datetime_strings),
                         visitor,
                     )
                 } else {
@@ -153,7 +167,8 @@ impl<'de> serde::de::VariantAccess<'de> for TableEnumDeserializer {
 
                 if tuple_values.len() == len {
                     serde::de::Deserializer::deserialize_seq(
-                        super::ArrayDeserializer::new(tuple_values, values_span),
+                        super::ArrayDeserializer::new(tuple_values, values_span)
+                            .with_datetime_strings(self.datetime_strings),
                         visitor,
                     )
                 } else {
@@ -179,7 +194,9 @@ impl<'de> serde::de::VariantAccess<'de> for TableEnumDeserializer {
         V: serde::de::Visitor<'de>,
     {
         serde::de::Deserializer::deserialize_struct(
-            super::ValueDeserializer::new(self.value).with_struct_key_validation(),
+            super::ValueDeserializer::new(self.value)
+                .with_struct_key_validation()
+                .with_datetime_strings(self.datetime_strings),
             "", // TODO: this should be the variant name
             fields,
             visitor,
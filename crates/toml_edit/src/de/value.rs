@@ -6,7 +6,11 @@ use crate::de::Error;
 /// Deserialization implementation for TOML [values][crate::Value].
 ///
 /// Can be created either directly from TOML strings, using [`std::str::FromStr`],
-/// or from parsed [values][crate::Value] using [`serde::de::IntoDeserializer::into_deserializer`].
+/// or from parsed [values][crate::Value], [tables][crate::Table] and [items][crate::Item] using
+/// [`serde::de::IntoDeserializer::into_deserializer`].
+///
+/// When created from a [table][crate::Table] or an [item][crate::Item], datetimes also
+/// deserialize as RFC 3339 strings into string fields.
 ///
 /// # Example
 ///
@@ -33,6 +37,7 @@ use crate::de::Error;
 pub struct ValueDeserializer {
     input: crate::Item,
     validate_struct_keys: bool,
+    datetime_strings: bool,
 }
 
 impl ValueDeserializer {
@@ -40,6 +45,7 @@ impl ValueDeserializer {
         Self {
             input,
             validate_struct_keys: false,
+            datetime_strings: false,
         }
     }
 
@@ -47,6 +53,11 @@ impl ValueDeserializer {
         self.validate_struct_keys = true;
         self
     }
+
+    pub(crate) fn with_datetime_strings(mut self, yes: bool) -> Self {
+        self.datetime_strings = yes;
+        self
+    }
 }
 
 // Note: this is wrapped by `toml::de::ValueDeserializer` and any trait methods
@@ -59,6 +70,7 @@ impl<'de> serde::Deserializer<'de> for ValueDeserializer {
         V: serde::de::Visitor<'de>,
     {
         let span = self.input.span();
+        let datetime_strings = self.datetime_strings;
         match self.input {
             crate::Item::None => visitor.visit_none(),
             crate::Item::Value(crate::Value::String(v)) => visitor.visit_string(v.into_value()),
@@ -68,14 +80,22 @@ impl<'de> serde::Deserializer<'de> for ValueDeserializer {
             crate::Item::Value(crate::Value::Datetime(v)) => {
                 visitor.visit_map(DatetimeDeserializer::new(v.into_value()))
             }
-            crate::Item::Value(crate::Value::Array(v)) => {
-                v.into_deserializer().deserialize_any(visitor)
-            }
-            crate::Item::Value(crate::Value::InlineTable(v)) => {
-                v.into_deserializer().deserialize_any(visitor)
-            }
-            crate::Item::Table(v) => v.into_deserializer().deserialize_any(visitor),
-            crate::Item::ArrayOfTables(v) => v.into_deserializer().deserialize_any(visitor),
+            crate::Item::Value(crate::Value::Array(v)) => v
+                .into_deserializer()
+                .with_datetime_strings(datetime_strings)
+                .deserialize_any(visitor),
+            crate::Item::Value(crate::Value::InlineTable(v)) => v
+                .into_deserializer()
+                .with_datetime_strings(datetime_strings)
+                .deserialize_any(visitor),
+            crate::Item::Table(v) => v
+                .into_deserializer()
+                .with_datetime_strings(datetime_strings)
+                .deserialize_any(visitor),
+            crate::Item::ArrayOfTables(v) => v
+                .into_deserializer()
+                .with_datetime_strings(datetime_strings)
+                .deserialize_any(visitor),
         }
         .map_err(|mut e: Self::Error| {
             if e.span().is_none() {
@@ -85,6 +105,35 @@ impl<'de> serde::Deserializer<'de> for ValueDeserializer {
         })
     }
 
+    // Datetimes are maps when deserialized through `deserialize_any`; for tables and items, give
+    // them as RFC 3339 strings when a string is asked for
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.input {
+            crate::Item::Value(crate::Value::Datetime(v)) if self.datetime_strings => {
+                let span = v.span();
+                visitor
+                    .visit_string(v.into_value().to_string())
+                    .map_err(|mut e: Self::Error| {
+                        if e.span().is_none() {
+                            e.set_span(span);
+                        }
+                        e
+                    })
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
     // `None` is interpreted as a missing field so be sure to implement `Some`
     // as a present field.
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
@@ -179,6 +228,7 @@ impl<'de> serde::Deserializer<'de> for ValueDeserializer {
         V: serde::de::Visitor<'de>,
     {
         let span = self.input.span();
+        let datetime_strings = self.datetime_strings;
         match self.input {
             crate::Item::Value(crate::Value::String(v)) => {
                 visitor.visit_enum(v.into_value().into_deserializer())
@@ -196,11 +246,13 @@ impl<'de> serde::Deserializer<'de> for ValueDeserializer {
                     ))
                 } else {
                     v.into_deserializer()
+                        .with_datetime_strings(datetime_strings)
                         .deserialize_enum(name, variants, visitor)
                 }
             }
             crate::Item::Table(v) => v
                 .into_deserializer()
+                .with_datetime_strings(datetime_strings)
                 .deserialize_enum(name, variants, visitor),
             e => Err(crate::de::Error::custom("wanted string or table", e.span())),
         }
@@ -213,7 +265,7 @@ impl<'de> serde::Deserializer<'de> for ValueDeserializer {
     }
 
     serde::forward_to_deserialize_any! {
-        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char str string seq
+        bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 char seq
         bytes byte_buf map unit
         ignored_any unit_struct tuple_struct tuple identifier
     }
@@ -235,6 +287,22 @@ impl<'de> serde::de::IntoDeserializer<'de, crate::de::Error> for crate::Value {
     }
 }
 
+impl<'de> serde::de::IntoDeserializer<'de, crate::de::Error> for crate::Item {
+    type Deserializer = ValueDeserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ValueDeserializer::new(self).with_datetime_strings(true)
+    }
+}
+
+impl<'de> serde::de::IntoDeserializer<'de, crate::de::Error> for crate::Table {
+    type Deserializer = ValueDeserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ValueDeserializer::new(crate::Item::Table(self)).with_datetime_strings(true)
+    }
+}
+
 impl crate::Item {
     pub(crate) fn into_deserializer(self) -> ValueDeserializer {
         ValueDeserializer::new(self)
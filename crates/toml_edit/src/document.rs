@@ -1,7 +1,8 @@
-use std::str::FromStr;
+use core::str::FromStr;
 
+use crate::prelude::*;
 use crate::table::Iter;
-use crate::{Item, RawString, Table};
+use crate::{Item, RawString, Table, Value};
 
 /// Type representing a TOML document
 #[derive(Debug, Clone)]
@@ -10,7 +11,166 @@ pub struct Document {
     // Trailing comments and whitespaces
     pub(crate) trailing: RawString,
     pub(crate) original: Option<String>,
-    pub(crate) span: Option<std::ops::Range<usize>>,
+    pub(crate) span: Option<core::ops::Range<usize>>,
+    pub(crate) line_ending: LineEnding,
+    // Whether the parsed input started with a byte order mark
+    pub(crate) bom: bool,
+}
+
+/// A difference between two documents, see [`Document::diff`]
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// `path` is only present in the new document
+    Added {
+        /// Location of the change
+        path: Vec<String>,
+        /// The new item
+        new: Item,
+    },
+    /// `path` is only present in the old document
+    Removed {
+        /// Location of the change
+        path: Vec<String>,
+        /// The old item
+        old: Item,
+    },
+    /// `path` holds a different value
+    Modified {
+        /// Location of the change
+        path: Vec<String>,
+        /// The old item
+        old: Item,
+        /// The new item
+        new: Item,
+    },
+}
+
+impl Change {
+    /// Location of the change
+    pub fn path(&self) -> &[String] {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Modified { path, .. } => path,
+        }
+    }
+}
+
+/// Size of a [`Document`], see [`Document::stats`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DocumentStats {
+    /// Tables, other than the root, including each table in an array of tables
+    pub tables: usize,
+    /// Arrays of tables
+    pub arrays_of_tables: usize,
+    /// Values, including those within arrays and inline tables
+    pub values: usize,
+    /// Keys of tables and inline tables
+    pub keys: usize,
+    /// Deepest nesting, where top-level keys are at depth 0
+    ///
+    /// Tables, arrays, and inline tables each add a level; an array of tables and its tables add
+    /// one level together.
+    pub max_depth: usize,
+}
+
+/// Newline style used when rendering a [`Document`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    Crlf,
+}
+
+/// A read-only copy of a [`Document`], see [`Document::snapshot`]
+///
+/// Cloning a snapshot only bumps a reference count.  No subtree is shared with the document it
+/// was taken from, so taking a snapshot still copies the whole document.
+#[derive(Debug, Clone)]
+pub struct DocumentSnapshot {
+    doc: alloc::sync::Arc<Document>,
+}
+
+impl DocumentSnapshot {
+    /// Returns an editable copy of the snapshot
+    ///
+    /// Like [`Document::snapshot`], this deep-copies the whole document.
+    pub fn to_document(&self) -> Document {
+        Document::clone(&self.doc)
+    }
+}
+
+impl core::ops::Deref for DocumentSnapshot {
+    type Target = Document;
+
+    fn deref(&self) -> &Self::Target {
+        &self.doc
+    }
+}
+
+impl From<Document> for DocumentSnapshot {
+    fn from(doc: Document) -> Self {
+        Self {
+            doc: alloc::sync::Arc::new(doc),
+        }
+    }
+}
+
+#[cfg(feature = "display")]
+impl core::fmt::Display for DocumentSnapshot {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.doc.fmt(f)
+    }
+}
+
+/// How [`Document::parse_with_options`] treats its input
+///
+/// Start from [`ParseOptions::default`] and adjust the fields of interest, as more may be added.
+#[cfg(feature = "parse")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Keep runs of blank lines as they are, rather than collapsing each into one blank line
+    pub preserve_blank_lines: bool,
+    /// Accept decimal numbers with leading zeros, see [`Document::parse_lenient_numbers`]
+    pub lenient_numbers: bool,
+    /// Accept integers above `i64::MAX`, up to `u64::MAX`, to be read with [`Value::as_u64`]
+    ///
+    /// TOML integers are signed 64-bit, so a document relying on this is not valid TOML and
+    /// other parsers will likely reject it.  Such integers keep their exact text when written
+    /// back, but [`Value::as_integer`] sees them as the `i64` with the same bits, like
+    /// `u64::MAX as i64`.
+    ///
+    /// [`Value::as_u64`]: crate::Value::as_u64
+    /// [`Value::as_integer`]: crate::Value::as_integer
+    pub u64_integers: bool,
+}
+
+#[cfg(feature = "parse")]
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            preserve_blank_lines: true,
+            lenient_numbers: false,
+            u64_integers: false,
+        }
+    }
+}
+
+impl LineEnding {
+    /// The dominant newline style of `input`, preferring `\n` in a tie
+    #[cfg(feature = "parse")]
+    pub(crate) fn detect(input: &str) -> Self {
+        let crlf = input.matches("\r\n").count();
+        let lf = input.matches('\n').count() - crlf;
+        if lf < crlf {
+            LineEnding::Crlf
+        } else {
+            LineEnding::Lf
+        }
+    }
 }
 
 impl Document {
@@ -19,6 +179,128 @@ impl Document {
         Default::default()
     }
 
+    /// Parses a document, accepting decimal numbers with leading zeros
+    ///
+    /// Strict TOML rejects `007` and `-01.5`; here the extra zeros are dropped, so they are read
+    /// and written back as `7` and `-1.5`.  No other deviation from the spec is accepted, and
+    /// `"...".parse::<Document>()` stays strict.  This is [`Document::parse_with_options`] with
+    /// [`ParseOptions::lenient_numbers`][crate::ParseOptions::lenient_numbers] set.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let doc = toml_edit::Document::parse_lenient_numbers("x = 007\n").unwrap();
+    /// assert_eq!(doc["x"].as_integer(), Some(7));
+    /// assert_eq!(doc.to_string(), "x = 7\n");
+    /// # }
+    /// ```
+    #[cfg(feature = "parse")]
+    pub fn parse_lenient_numbers(input: &str) -> Result<Self, crate::TomlError> {
+        let options = crate::ParseOptions {
+            lenient_numbers: true,
+            ..Default::default()
+        };
+        Self::parse_with_options(input, options)
+    }
+
+    /// Parses a document, adjusting its formatting according to `options`
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut options = toml_edit::ParseOptions::default();
+    /// options.preserve_blank_lines = false;
+    /// let doc = toml_edit::Document::parse_with_options("a = 1\n\n\n\nb = 2\n", options).unwrap();
+    /// assert_eq!(doc.to_string(), "a = 1\n\nb = 2\n");
+    /// # }
+    /// ```
+    #[cfg(feature = "parse")]
+    pub fn parse_with_options(
+        input: &str,
+        options: crate::ParseOptions,
+    ) -> Result<Self, crate::TomlError> {
+        let ext = crate::parser::prelude::Extensions {
+            leading_zeros: options.lenient_numbers,
+            u64_integers: options.u64_integers,
+        };
+        let mut d = crate::parser::parse_document_with(input, ext)?;
+        d.despan();
+        if options.lenient_numbers {
+            strip_leading_zeros(&mut d);
+        }
+        if !options.preserve_blank_lines {
+            collapse_blank_lines(&mut d);
+        }
+        Ok(d)
+    }
+
+    /// Parses a document, also reporting constructs that are valid TOML but discouraged
+    ///
+    /// Arrays mixing value types, which TOML 0.5 rejected, are flagged with their span in
+    /// `input`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let (doc, warnings) = toml_edit::Document::parse_with_warnings("a = [1, \"x\"]\n").unwrap();
+    /// assert_eq!(doc["a"].as_array().unwrap().len(), 2);
+    /// assert_eq!(warnings.len(), 1);
+    /// assert_eq!(warnings[0].span(), Some(4..12));
+    /// # }
+    /// ```
+    #[cfg(feature = "parse")]
+    pub fn parse_with_warnings(
+        input: &str,
+    ) -> Result<(Self, Vec<crate::Warning>), crate::TomlError> {
+        let mut d = crate::parser::parse_document(input)?;
+        let warnings = collect_warnings(&d);
+        d.despan();
+        Ok((d, warnings))
+    }
+
+    /// Parses a document, skipping the lines that fail to parse rather than giving up
+    ///
+    /// Every skipped part is reported with its error, and left as blank lines in the document,
+    /// keeping their line endings.
+    /// An unterminated string is skipped up to the end of its line, or up to the next table
+    /// header for a multi-line string.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let (doc, errors) = toml_edit::Document::parse_recover("a = \"oops\nb = 2\n");
+    /// assert_eq!(errors.len(), 1);
+    /// assert!(!doc.contains_key("a"));
+    /// assert_eq!(doc["b"].as_integer(), Some(2));
+    /// # }
+    /// ```
+    #[cfg(feature = "parse")]
+    pub fn parse_recover(input: &str) -> (Self, Vec<crate::TomlError>) {
+        let (mut d, errors) = crate::parser::parse_document_recover(input);
+        d.despan();
+        (d, errors)
+    }
+
+    /// Checks whether rendering this document reproduces `original` exactly
+    ///
+    /// A document parsed from valid TOML and left unmodified renders back byte-for-byte,
+    /// including comments, whitespace, string and number formats, and a leading byte order
+    /// mark.  The one normalization is line endings: input mixing `\n` and `\r\n` is written
+    /// with whichever is more common, see [`Document::line_ending`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let input = "a = [ 1,2 ] # numbers\n";
+    /// let doc = input.parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.is_byte_identical_to(input));
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn is_byte_identical_to(&self, original: &str) -> bool {
+        self.to_string() == original
+    }
+
     /// Returns a reference to the root item.
     pub fn as_item(&self) -> &Item {
         &self.root
@@ -56,6 +338,455 @@ impl Document {
         &self.trailing
     }
 
+    /// Set the newline style used when rendering
+    ///
+    /// Parsed documents default to the dominant newline style of their input.
+    pub fn set_line_ending(&mut self, line_ending: LineEnding) {
+        self.line_ending = line_ending;
+    }
+
+    /// The newline style used when rendering
+    pub fn line_ending(&self) -> LineEnding {
+        self.line_ending
+    }
+
+    /// Set the number of blank lines before each table header
+    ///
+    /// Comments before a header are kept; only the blank lines before them are replaced.  The
+    /// first header is left alone unless there are values before it.
+    pub fn set_section_spacing(&mut self, blank_lines: usize) {
+        let mut last_position = 0;
+        let mut headers = Vec::new();
+        collect_headers(self.as_table(), 0, false, &mut last_position, &mut headers);
+        let first = if self.as_table().get_values().is_empty() {
+            let first_position = headers
+                .iter()
+                .filter(|(_, visible)| *visible)
+                .map(|(position, _)| *position)
+                .min();
+            headers
+                .iter()
+                .position(|&(position, visible)| visible && Some(position) == first_position)
+        } else {
+            None
+        };
+
+        let mut index = 0;
+        respace_headers(
+            self.as_table_mut(),
+            0,
+            false,
+            &mut index,
+            first,
+            blank_lines,
+        );
+    }
+
+    /// Indent the keys under each table header by `spaces`
+    ///
+    /// Comments before those keys are indented along with them, while keys before the first
+    /// header are left alone.  Use `0` to remove indentation.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "a = 1\n[section]\nb = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.set_indent(2);
+    /// assert_eq!(doc.to_string(), "a = 1\n[section]\n  b = 2\n");
+    /// # }
+    /// ```
+    pub fn set_indent(&mut self, spaces: usize) {
+        let indent = " ".repeat(spaces);
+        indent_table(self.as_table_mut(), true, &indent);
+    }
+
+    /// Normalize how every key is quoted
+    ///
+    /// Keys that are not valid bare keys stay quoted under
+    /// [`KeyQuoteStyle::AlwaysBare`][crate::KeyQuoteStyle::AlwaysBare].
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "\"a\" = 1\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.set_key_quote_style(toml_edit::KeyQuoteStyle::AlwaysBare);
+    /// assert_eq!(doc.to_string(), "a = 1\n");
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn set_key_quote_style(&mut self, style: crate::KeyQuoteStyle) {
+        use crate::visit_mut::{visit_table_like_kv_mut, VisitMut};
+        use crate::KeyMut;
+
+        struct Quoter(crate::KeyQuoteStyle);
+
+        impl VisitMut for Quoter {
+            fn visit_table_like_kv_mut(&mut self, mut key: KeyMut<'_>, node: &mut Item) {
+                key.set_quote_style(self.0);
+                visit_table_like_kv_mut(self, key, node);
+            }
+        }
+
+        Quoter(style).visit_document_mut(self);
+    }
+
+    /// Normalize how every string value is quoted
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "path = \"C:\\\\temp\"\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.set_string_quote_style(toml_edit::StringQuoteStyle::Minimal);
+    /// assert_eq!(doc.to_string(), "path = 'C:\\temp'\n");
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn set_string_quote_style(&mut self, style: crate::StringQuoteStyle) {
+        use crate::repr::Formatted;
+        use crate::visit_mut::VisitMut;
+        use crate::StringQuoteStyle;
+
+        struct Quoter(StringQuoteStyle);
+
+        impl VisitMut for Quoter {
+            fn visit_string_mut(&mut self, node: &mut Formatted<String>) {
+                let value = node.value();
+                let repr = match self.0 {
+                    StringQuoteStyle::Preserve => return,
+                    StringQuoteStyle::Minimal => crate::encode::to_minimal_string_repr(value),
+                    StringQuoteStyle::AlwaysBasic => {
+                        crate::encode::to_string_repr(value, None, Some(false))
+                    }
+                };
+                node.set_repr_unchecked(repr);
+            }
+        }
+
+        Quoter(style).visit_document_mut(self);
+    }
+
+    /// Lay out each array with one element per line when its single-line form is longer than
+    /// `max_inline_len`, and on a single line otherwise
+    ///
+    /// The single-line form is measured without the key, with nested arrays already laid out.
+    /// Arrays holding comments, and arrays within inline tables, are left alone.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "short = [1, 2]\nlong = [\"alpha\", \"beta\"]\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// doc.set_array_wrap(10);
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "short = [1, 2]\nlong = [\n    \"alpha\",\n    \"beta\",\n]\n"
+    /// );
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn set_array_wrap(&mut self, max_inline_len: usize) {
+        use crate::visit_mut::VisitMut;
+        use crate::{Array, InlineTable};
+
+        struct Wrapper(usize);
+
+        impl VisitMut for Wrapper {
+            fn visit_array_mut(&mut self, node: &mut Array) {
+                wrap_array(node, self.0, 1);
+            }
+
+            fn visit_inline_table_mut(&mut self, _node: &mut InlineTable) {}
+        }
+
+        Wrapper(max_inline_len).visit_document_mut(self);
+    }
+
+    /// Pad the keys of consecutive assignments so their `=` signs line up, or with `false`,
+    /// put a single space before each `=`
+    ///
+    /// Each table is aligned on its own, and a blank line or a dotted key starts a new group.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "[package]\nname = \"foo\"\nversion = \"1.0\"\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// doc.align_assignments(true);
+    /// assert_eq!(
+    ///     doc.to_string(),
+    ///     "[package]\nname    = \"foo\"\nversion = \"1.0\"\n"
+    /// );
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn align_assignments(&mut self, align: bool) {
+        align_table(self.as_table_mut(), align);
+    }
+
+    /// Number of bytes [`Display`][core::fmt::Display] would render, without building the string
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let doc = "a = 1\n[t]\nb = 'x'\n".parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(doc.rendered_len(), doc.to_string().len());
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn rendered_len(&self) -> usize {
+        self.measure().bytes
+    }
+
+    /// Number of lines [`Display`][core::fmt::Display] would render, counted like [`str::lines`]
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let doc = "a = 1\n[t]\nb = 'x'\n".parse::<toml_edit::Document>().unwrap();
+    /// assert_eq!(doc.line_count(), 3);
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn line_count(&self) -> usize {
+        let measure = self.measure();
+        measure.newlines + usize::from(measure.open_line)
+    }
+
+    #[cfg(feature = "display")]
+    fn measure(&self) -> Measure {
+        use core::fmt::Write;
+
+        let mut measure = Measure::default();
+        write!(measure, "{}", self).expect("measuring is infallible");
+        measure
+    }
+
+    /// Takes a read-only copy of the document that is cheap to clone
+    ///
+    /// The snapshot is unaffected by later changes to the document.  Taking it deep-copies the
+    /// whole document, as no subtree is shared with it, so it costs as much as [`Clone::clone`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "a = 1\n".parse::<toml_edit::Document>().unwrap();
+    /// let before = doc.snapshot();
+    /// doc["a"] = toml_edit::value(2);
+    /// assert_eq!(before["a"].as_integer(), Some(1));
+    /// doc = before.to_document();
+    /// assert_eq!(doc.to_string(), "a = 1\n");
+    /// # }
+    /// ```
+    pub fn snapshot(&self) -> DocumentSnapshot {
+        self.clone().into()
+    }
+
+    /// Count the tables, values, and keys in the document
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let doc = "a = 1\n[b]\nc = [2, 3]\n".parse::<toml_edit::Document>().unwrap();
+    /// let stats = doc.stats();
+    /// assert_eq!(stats.tables, 1);
+    /// assert_eq!(stats.values, 4);
+    /// assert_eq!(stats.max_depth, 2);
+    /// # }
+    /// ```
+    pub fn stats(&self) -> DocumentStats {
+        let mut stats = DocumentStats::default();
+        count_table(self.as_table(), 0, &mut stats);
+        stats
+    }
+
+    /// Removes the item at `path`, returning it
+    ///
+    /// Each segment of `path` is a key, descending through tables and inline tables.  Tables
+    /// left empty by the removal are kept.  Returns `None` if `path` is empty or does not exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "[a.b]\nc = 1\nd = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// let removed = doc.remove_path(&["a", "b", "c"]).unwrap();
+    /// assert_eq!(removed.as_integer(), Some(1));
+    /// assert_eq!(doc.to_string(), "[a.b]\nd = 2\n");
+    /// # }
+    /// ```
+    pub fn remove_path(&mut self, path: &[&str]) -> Option<Item> {
+        let (last, parents) = path.split_last()?;
+        let mut table: &mut dyn crate::TableLike = self.as_table_mut();
+        for key in parents {
+            table = table.get_mut(key)?.as_table_like_mut()?;
+        }
+        table.remove(last)
+    }
+
+    /// Sets the value at `path`, creating missing tables along the way
+    ///
+    /// Missing tables are created as implicit, so only the innermost one gets a header, or as
+    /// inline tables within an inline table.  An existing leaf is overwritten.
+    ///
+    /// # Panics
+    ///
+    /// If `path` is empty, or one of its tables already exists as something else.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = toml_edit::Document::new();
+    /// doc.set_value(&["a", "b", "c"], 5);
+    /// assert_eq!(doc.to_string(), "[a.b]\nc = 5\n");
+    /// # }
+    /// ```
+    pub fn set_value<V: Into<Value>>(&mut self, path: &[&str], v: V) {
+        let (leaf, parents) = path.split_last().expect("path must not be empty");
+        let mut table: &mut dyn crate::TableLike = self.as_table_mut();
+        let mut is_inline = false;
+        for key in parents {
+            let entry = table.entry(key).or_insert(Item::None);
+            if entry.is_none() {
+                *entry = if is_inline {
+                    Item::Value(Value::InlineTable(crate::InlineTable::new()))
+                } else {
+                    let mut new_table = Table::new();
+                    new_table.set_implicit(true);
+                    Item::Table(new_table)
+                };
+            }
+            is_inline |= entry.is_inline_table();
+            let type_name = entry.type_name();
+            table = entry.as_table_like_mut().unwrap_or_else(|| {
+                panic!(
+                    "cannot set `{}`, `{}` ({}) is not a table",
+                    path.join("."),
+                    key,
+                    type_name
+                )
+            });
+        }
+        table.insert(leaf, Item::Value(v.into()));
+    }
+
+    /// Sets every value of `patch` at the same path in this document
+    ///
+    /// Tables and inline tables are descended into, with missing ones created like in
+    /// [`Document::set_value`].  Anything else in `patch`, including arrays and arrays of
+    /// tables, replaces what is at its path wholesale, keeping the decor of a replaced value.
+    /// Keys missing from `patch` are left untouched.
+    ///
+    /// Unlike [`Table::merge_with`], the formatting of `patch` is not carried over.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "[server]\nhost = \"a\" # primary\nports = [1, 2]\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// let patch = "server.host = \"b\"\nserver.ports = [3]\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// doc.apply_patch(&patch);
+    /// assert_eq!(doc.to_string(), "[server]\nhost = \"b\" # primary\nports = [3]\n");
+    /// # }
+    /// ```
+    pub fn apply_patch(&mut self, patch: &Document) {
+        patch_table_like(self.as_table_mut(), patch.as_table(), false);
+    }
+
+    /// Removes tables left without any key/value pairs or non-empty subtables
+    ///
+    /// Tables are pruned bottom-up, so a parent only holding empty tables is removed too.  Tables
+    /// within an array of tables are pruned but never removed, as each one is an element.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "a = 1\n[b]\n[c.d]\n".parse::<toml_edit::Document>().unwrap();
+    /// doc.prune_empty_tables();
+    /// assert_eq!(doc.to_string(), "a = 1\n");
+    /// # }
+    /// ```
+    pub fn prune_empty_tables(&mut self) {
+        prune_table(self.as_table_mut());
+    }
+
+    /// Find every value in the document matching `pred`
+    ///
+    /// `pred` is called with the path to each value, including arrays and inline tables before
+    /// their contents.  Array elements and arrays of tables use the element's index as the path
+    /// segment.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let doc = "a = 1\n[b]\nc = 200\n".parse::<toml_edit::Document>().unwrap();
+    /// let found = doc.find_all(|_, v| v.as_integer().map_or(false, |i| i > 100));
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].0, ["b", "c"]);
+    /// # }
+    /// ```
+    pub fn find_all<F>(&self, pred: F) -> Vec<(Vec<String>, &Value)>
+    where
+        F: Fn(&[&str], &Value) -> bool,
+    {
+        let mut path = Vec::new();
+        let mut found = Vec::new();
+        find_in_item(&self.root, &mut path, &pred, &mut found);
+        found
+    }
+
+    /// Lists the dotted path of every table and value in the document, in document order
+    ///
+    /// Segments are written like keys in TOML, quoted unless they are valid bare keys, so a key
+    /// containing a `.` cannot be mistaken for two segments.  Each table of an array of tables is
+    /// listed with its index, like `bin[0]`.  Arrays and inline tables are listed before their
+    /// contents, though array elements are not listed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let doc = "a = 1\n[t]\n\"x.y\" = 2\n[[bin]]\nname = \"b\"\n"
+    ///     .parse::<toml_edit::Document>()
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     doc.all_key_paths(),
+    ///     ["a", "t", "t.\"x.y\"", "bin[0]", "bin[0].name"]
+    /// );
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn all_key_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        collect_key_paths(self.as_table(), "", &mut paths);
+        paths
+    }
+
+    /// Compare the content of two documents, ignoring formatting
+    ///
+    /// Tables and inline tables are compared key by key and arrays element by element, using the
+    /// same paths as [`Document::find_all`].  A value that changes type is reported as a single
+    /// [`Change::Modified`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let old = "a = 1\nb = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// let new = "a = 1\nb = 3 # changed\n".parse::<toml_edit::Document>().unwrap();
+    /// let changes = old.diff(&new);
+    /// assert_eq!(changes.len(), 1);
+    /// assert_eq!(changes[0].path(), ["b"]);
+    /// # }
+    /// ```
+    pub fn diff(&self, other: &Document) -> Vec<Change> {
+        let mut path = Vec::new();
+        let mut changes = Vec::new();
+        diff_item(&self.root, &other.root, &mut path, &mut changes);
+        changes
+    }
+
     /// # Panics
     ///
     /// If run on on a `Document` not generated by the parser
@@ -66,6 +797,638 @@ impl Document {
     }
 }
 
+/// Rewrites the repr of each integer and float with leading zeros into its canonical form,
+/// e.g. `007` into `7`
+#[cfg(feature = "parse")]
+fn strip_leading_zeros(doc: &mut Document) {
+    use crate::repr::ValueRepr;
+    use crate::visit_mut::VisitMut;
+    use crate::{Formatted, Repr};
+
+    struct Stripper;
+
+    impl VisitMut for Stripper {
+        fn visit_float_mut(&mut self, node: &mut Formatted<f64>) {
+            strip(node);
+        }
+
+        fn visit_integer_mut(&mut self, node: &mut Formatted<i64>) {
+            strip(node);
+        }
+    }
+
+    /// Drop the zeros that `Extensions::leading_zeros` let through, keeping any sign
+    fn strip<T: ValueRepr>(node: &mut Formatted<T>) {
+        let raw = match node.as_repr().and_then(|r| r.as_raw().as_str()) {
+            Some(raw) => raw,
+            None => return,
+        };
+        let bytes = raw.as_bytes();
+        let sign = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+        let mut zeros = 0;
+        while bytes.get(sign + zeros) == Some(&b'0')
+            && bytes
+                .get(sign + zeros + 1)
+                .map_or(false, u8::is_ascii_digit)
+        {
+            zeros += 1;
+        }
+        if zeros != 0 {
+            let stripped = format!("{}{}", &raw[..sign], &raw[sign + zeros..]);
+            node.set_repr_unchecked(Repr::new_unchecked(stripped));
+        }
+    }
+
+    Stripper.visit_document_mut(doc);
+}
+
+/// Collapses each run of blank lines in the document's whitespace into a single blank line
+#[cfg(feature = "parse")]
+fn collapse_blank_lines(doc: &mut Document) {
+    use crate::visit_mut::{
+        visit_array_mut, visit_inline_table_mut, visit_table_like_kv_mut, visit_table_mut, VisitMut,
+    };
+    use crate::{Array, Decor, InlineTable, KeyMut};
+
+    /// Whitespace outside of arrays and inline tables starts at the beginning of a line
+    struct Collapser {
+        nested: bool,
+    }
+
+    impl Collapser {
+        fn collapse_decor(&self, decor: &mut Decor) {
+            if let Some(prefix) = decor.prefix().and_then(|p| collapse(p, !self.nested)) {
+                decor.set_prefix(prefix);
+            }
+            if let Some(suffix) = decor.suffix().and_then(|s| collapse(s, false)) {
+                decor.set_suffix(suffix);
+            }
+        }
+    }
+
+    impl VisitMut for Collapser {
+        fn visit_table_mut(&mut self, node: &mut Table) {
+            self.collapse_decor(node.decor_mut());
+            visit_table_mut(self, node);
+        }
+
+        fn visit_inline_table_mut(&mut self, node: &mut InlineTable) {
+            let nested = core::mem::replace(&mut self.nested, true);
+            visit_inline_table_mut(self, node);
+            self.nested = nested;
+        }
+
+        fn visit_table_like_kv_mut(&mut self, mut key: KeyMut<'_>, node: &mut Item) {
+            self.collapse_decor(key.decor_mut());
+            if let Item::Value(value) = node {
+                self.collapse_decor(value.decor_mut());
+            }
+            visit_table_like_kv_mut(self, key, node);
+        }
+
+        fn visit_array_mut(&mut self, node: &mut Array) {
+            let nested = core::mem::replace(&mut self.nested, true);
+            for value in node.iter_mut() {
+                self.collapse_decor(value.decor_mut());
+            }
+            if let Some(trailing) = collapse(node.trailing(), false) {
+                node.set_trailing(trailing);
+            }
+            visit_array_mut(self, node);
+            self.nested = nested;
+        }
+    }
+
+    /// The collapsed whitespace, if `raw` has any run of blank lines
+    fn collapse(raw: &RawString, at_line_start: bool) -> Option<String> {
+        let raw = raw.as_str()?;
+        let segments = raw.split('\n').collect::<Vec<_>>();
+        let mut collapsed = String::with_capacity(raw.len());
+        let mut changed = false;
+        let mut previous_blank = false;
+        for (i, segment) in segments.iter().enumerate() {
+            let terminated = i + 1 < segments.len();
+            // The first segment ends a line that started before `raw`
+            let blank = terminated && (i != 0 || at_line_start) && segment.trim().is_empty();
+            if blank && previous_blank {
+                changed = true;
+            } else {
+                collapsed.push_str(segment);
+                if terminated {
+                    collapsed.push('\n');
+                }
+            }
+            previous_blank = blank;
+        }
+        changed.then_some(collapsed)
+    }
+
+    Collapser { nested: false }.visit_document_mut(doc);
+    if let Some(trailing) = collapse(doc.trailing(), true) {
+        doc.set_trailing(trailing);
+    }
+}
+
+fn patch_table_like(
+    target: &mut dyn crate::TableLike,
+    patch: &dyn crate::TableLike,
+    is_inline: bool,
+) {
+    for (key, item) in patch.iter() {
+        if let Some(patch) = item.as_table_like() {
+            let existing = target.entry(key).or_insert(Item::None);
+            if !existing.is_table_like() {
+                *existing = if is_inline || item.is_inline_table() {
+                    Item::Value(Value::InlineTable(crate::InlineTable::new()))
+                } else {
+                    let mut new_table = Table::new();
+                    new_table.set_implicit(true);
+                    Item::Table(new_table)
+                };
+            }
+            let is_inline = is_inline || existing.is_inline_table();
+            let existing = existing.as_table_like_mut().expect("checked above");
+            patch_table_like(existing, patch, is_inline);
+            continue;
+        }
+        let mut item = item.clone();
+        if let Some(value) = item.as_value_mut() {
+            value.decor_mut().clear();
+        }
+        if is_inline {
+            item.make_value();
+        }
+        match target.get_mut(key) {
+            Some(Item::Value(existing)) if item.is_value() => {
+                let decor = existing.decor().clone();
+                *existing = item.into_value().expect("checked above");
+                *existing.decor_mut() = decor;
+            }
+            Some(existing) => *existing = item,
+            None => {
+                target.insert(key, item);
+            }
+        }
+    }
+}
+
+/// Flags the discouraged constructs of a document that still has its spans
+#[cfg(feature = "parse")]
+fn collect_warnings(doc: &Document) -> Vec<crate::Warning> {
+    use crate::visit::{visit_array, Visit};
+    use crate::Array;
+
+    struct Collector(Vec<crate::Warning>);
+
+    impl<'doc> Visit<'doc> for Collector {
+        fn visit_array(&mut self, node: &'doc Array) {
+            let mut kinds = node.iter().map(Value::kind);
+            if let Some(first) = kinds.next() {
+                if let Some(other) = kinds.find(|kind| *kind != first) {
+                    self.0.push(crate::Warning::new(
+                        format!("array mixes {first} and {other} values, which TOML 0.5 rejects"),
+                        node.span(),
+                    ));
+                }
+            }
+            visit_array(self, node);
+        }
+    }
+
+    let mut collector = Collector(Vec::new());
+    collector.visit_document(doc);
+    collector.0
+}
+
+/// Records the render position and visibility of each table header, in visiting order
+fn collect_headers(
+    table: &Table,
+    path_len: usize,
+    is_array_of_tables: bool,
+    last_position: &mut usize,
+    headers: &mut Vec<(usize, bool)>,
+) {
+    if !table.is_dotted() {
+        if let Some(position) = table.position() {
+            *last_position = position;
+        }
+        if path_len != 0 {
+            let visible = is_array_of_tables || is_header_visible(table);
+            headers.push((*last_position, visible));
+        }
+    }
+    for kv in table.items.values() {
+        match &kv.value {
+            Item::Table(t) => collect_headers(t, path_len + 1, false, last_position, headers),
+            Item::ArrayOfTables(a) => {
+                for t in a.iter() {
+                    collect_headers(t, path_len + 1, true, last_position, headers);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn respace_headers(
+    table: &mut Table,
+    path_len: usize,
+    is_array_of_tables: bool,
+    index: &mut usize,
+    first: Option<usize>,
+    blank_lines: usize,
+) {
+    if !table.is_dotted() && path_len != 0 {
+        let visible = is_array_of_tables || is_header_visible(table);
+        if visible && Some(*index) != first {
+            let decor = table.decor_mut();
+            let prefix = decor.prefix().and_then(RawString::as_str).unwrap_or("");
+            let (_, rest) = crate::repr::split_blank_lines(prefix);
+            let prefix = format!("{}{}", "\n".repeat(blank_lines), rest);
+            decor.set_prefix(prefix);
+        }
+        *index += 1;
+    }
+    for kv in table.items.values_mut() {
+        match &mut kv.value {
+            Item::Table(t) => respace_headers(t, path_len + 1, false, index, first, blank_lines),
+            Item::ArrayOfTables(a) => {
+                for t in a.iter_mut() {
+                    respace_headers(t, path_len + 1, true, index, first, blank_lines);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn indent_table(table: &mut Table, is_root: bool, indent: &str) {
+    for kv in table.items.values_mut() {
+        let is_body = match &kv.value {
+            Item::Value(_) => true,
+            Item::Table(t) => t.is_dotted(),
+            _ => false,
+        };
+        if is_body && !is_root {
+            let decor = kv.key.decor_mut();
+            let prefix = decor.prefix().and_then(RawString::as_str).unwrap_or("");
+            let prefix = indent_prefix(prefix, indent);
+            decor.set_prefix(prefix);
+        }
+        match &mut kv.value {
+            Item::Table(t) if !t.is_dotted() => indent_table(t, false, indent),
+            Item::ArrayOfTables(a) => {
+                for t in a.iter_mut() {
+                    indent_table(t, false, indent);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(feature = "display")]
+fn align_table(table: &mut Table, align: bool) {
+    let mut group = Vec::new();
+    for kv in table.items.values_mut() {
+        match &kv.value {
+            Item::Value(_) => {
+                let prefix = kv.key.decor().prefix().and_then(RawString::as_str);
+                let mut lines = prefix.unwrap_or("").rsplit('\n');
+                let indent = lines.next().unwrap_or("");
+                if lines.any(|line| line.trim().is_empty()) {
+                    align_keys(&mut group, align);
+                }
+                let width = indent.chars().count() + kv.key.display_repr().chars().count();
+                group.push((&mut kv.key, width));
+            }
+            Item::Table(t) if t.is_dotted() => align_keys(&mut group, align),
+            _ => {}
+        }
+    }
+    align_keys(&mut group, align);
+
+    for kv in table.items.values_mut() {
+        match &mut kv.value {
+            Item::Table(t) if !t.is_dotted() => align_table(t, align),
+            Item::ArrayOfTables(a) => {
+                for t in a.iter_mut() {
+                    align_table(t, align);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Pads each key, paired with the width of its line up to the key's end, to the widest one
+#[cfg(feature = "display")]
+fn align_keys(group: &mut Vec<(&mut crate::Key, usize)>, align: bool) {
+    let column = group.iter().map(|(_, width)| *width).max().unwrap_or(0);
+    for (key, width) in group.drain(..) {
+        let padding = if align { column - width + 1 } else { 1 };
+        key.decor_mut().set_suffix(" ".repeat(padding));
+    }
+}
+
+/// Re-indents the comment lines of a key's prefix and the key itself
+fn indent_prefix(prefix: &str, indent: &str) -> String {
+    let mut lines = prefix.split('\n').collect::<Vec<_>>();
+    lines.pop();
+    let mut indented = String::new();
+    for line in lines {
+        let line = line.trim_start();
+        if !line.is_empty() {
+            indented.push_str(indent);
+            indented.push_str(line);
+        }
+        indented.push('\n');
+    }
+    indented.push_str(indent);
+    indented
+}
+
+/// Sizes of rendered output, see [`Document::rendered_len`]
+#[cfg(feature = "display")]
+#[derive(Default)]
+struct Measure {
+    bytes: usize,
+    newlines: usize,
+    // Whether the last line has content without a terminating newline
+    open_line: bool,
+}
+
+#[cfg(feature = "display")]
+impl core::fmt::Write for Measure {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        if let Some(last) = s.bytes().last() {
+            self.bytes += s.len();
+            self.newlines += s.bytes().filter(|b| *b == b'\n').count();
+            self.open_line = last != b'\n';
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "display")]
+fn wrap_array(array: &mut crate::Array, max_inline_len: usize, depth: usize) {
+    for value in array.iter_mut() {
+        if let Value::Array(nested) = value {
+            wrap_array(nested, max_inline_len, depth + 1);
+        }
+    }
+
+    let has_comment = |raw: Option<&RawString>| {
+        raw.and_then(RawString::as_str)
+            .map_or(false, |s| s.contains('#'))
+    };
+    if has_comment(Some(array.trailing()))
+        || array
+            .iter()
+            .any(|v| has_comment(v.decor().prefix()) || has_comment(v.decor().suffix()))
+    {
+        return;
+    }
+
+    array.fmt();
+    if array.to_string().len() > max_inline_len {
+        let indent = "    ".repeat(depth);
+        for value in array.iter_mut() {
+            value.decor_mut().set_prefix(format!("\n{indent}"));
+        }
+        array.set_trailing_comma(true);
+        array.set_trailing(format!("\n{}", &indent[4..]));
+    }
+}
+
+/// Whether a header is rendered, see `encode::visit_table`
+fn is_header_visible(table: &Table) -> bool {
+    !(table.is_implicit() && table.get_values().is_empty())
+}
+
+fn prune_table(table: &mut Table) {
+    table.items.retain(|_, kv| match &mut kv.value {
+        Item::Table(t) => {
+            prune_table(t);
+            !t.is_empty()
+        }
+        Item::ArrayOfTables(a) => {
+            for t in a.iter_mut() {
+                prune_table(t);
+            }
+            true
+        }
+        _ => true,
+    });
+}
+
+fn count_table(table: &Table, depth: usize, stats: &mut DocumentStats) {
+    stats.max_depth = stats.max_depth.max(depth);
+    for (_, item) in table.iter() {
+        stats.keys += 1;
+        match item {
+            Item::None => {}
+            Item::Value(value) => count_value(value, depth, stats),
+            Item::Table(table) => {
+                stats.tables += 1;
+                count_table(table, depth + 1, stats);
+            }
+            Item::ArrayOfTables(array) => {
+                stats.arrays_of_tables += 1;
+                for table in array.iter() {
+                    stats.tables += 1;
+                    count_table(table, depth + 1, stats);
+                }
+            }
+        }
+    }
+}
+
+fn count_value(value: &Value, depth: usize, stats: &mut DocumentStats) {
+    stats.values += 1;
+    stats.max_depth = stats.max_depth.max(depth);
+    match value {
+        Value::Array(array) => {
+            for value in array.iter() {
+                count_value(value, depth + 1, stats);
+            }
+        }
+        Value::InlineTable(table) => {
+            for (_, value) in table.iter() {
+                stats.keys += 1;
+                count_value(value, depth + 1, stats);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(feature = "display")]
+fn collect_key_paths(table: &dyn crate::TableLike, prefix: &str, paths: &mut Vec<String>) {
+    for (key, item) in table.iter() {
+        let key = crate::Key::new(key);
+        let path = if prefix.is_empty() {
+            key.display_repr().into_owned()
+        } else {
+            format!("{}.{}", prefix, key.display_repr())
+        };
+        if let Item::ArrayOfTables(array) = item {
+            for (i, table) in array.iter().enumerate() {
+                let path = format!("{path}[{i}]");
+                paths.push(path.clone());
+                collect_key_paths(table, &path, paths);
+            }
+        } else {
+            paths.push(path.clone());
+            if let Some(table) = item.as_table_like() {
+                collect_key_paths(table, &path, paths);
+            }
+        }
+    }
+}
+
+fn find_in_item<'d, F>(
+    item: &'d Item,
+    path: &mut Vec<String>,
+    pred: &F,
+    found: &mut Vec<(Vec<String>, &'d Value)>,
+) where
+    F: Fn(&[&str], &Value) -> bool,
+{
+    match item {
+        Item::None => {}
+        Item::Value(value) => find_in_value(value, path, pred, found),
+        Item::Table(table) => {
+            for (key, item) in table.iter() {
+                path.push(key.to_owned());
+                find_in_item(item, path, pred, found);
+                path.pop();
+            }
+        }
+        Item::ArrayOfTables(array) => {
+            for (i, table) in array.iter().enumerate() {
+                path.push(i.to_string());
+                for (key, item) in table.iter() {
+                    path.push(key.to_owned());
+                    find_in_item(item, path, pred, found);
+                    path.pop();
+                }
+                path.pop();
+            }
+        }
+    }
+}
+
+fn find_in_value<'d, F>(
+    value: &'d Value,
+    path: &mut Vec<String>,
+    pred: &F,
+    found: &mut Vec<(Vec<String>, &'d Value)>,
+) where
+    F: Fn(&[&str], &Value) -> bool,
+{
+    let segments = path.iter().map(String::as_str).collect::<Vec<_>>();
+    if pred(&segments, value) {
+        found.push((path.clone(), value));
+    }
+    match value {
+        Value::Array(array) => {
+            for (i, value) in array.iter().enumerate() {
+                path.push(i.to_string());
+                find_in_value(value, path, pred, found);
+                path.pop();
+            }
+        }
+        Value::InlineTable(table) => {
+            for (key, value) in table.iter() {
+                path.push(key.to_owned());
+                find_in_value(value, path, pred, found);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn diff_item(old: &Item, new: &Item, path: &mut Vec<String>, changes: &mut Vec<Change>) {
+    if let (Some(old), Some(new)) = (old.as_table_like(), new.as_table_like()) {
+        for (key, old) in old.iter() {
+            path.push(key.to_owned());
+            match new.get(key).filter(|new| !new.is_none()) {
+                Some(new) => diff_item(old, new, path, changes),
+                None => changes.push(Change::Removed {
+                    path: path.clone(),
+                    old: old.clone(),
+                }),
+            }
+            path.pop();
+        }
+        for (key, new) in new.iter() {
+            if old.get(key).map_or(true, Item::is_none) {
+                path.push(key.to_owned());
+                changes.push(Change::Added {
+                    path: path.clone(),
+                    new: new.clone(),
+                });
+                path.pop();
+            }
+        }
+    } else if let (Some(old), Some(new)) = (old.as_array_of_tables(), new.as_array_of_tables()) {
+        let old = old
+            .iter()
+            .map(|t| Item::Table(t.clone()))
+            .collect::<Vec<_>>();
+        let new = new
+            .iter()
+            .map(|t| Item::Table(t.clone()))
+            .collect::<Vec<_>>();
+        diff_elements(&old, &new, path, changes);
+    } else if let (Some(old), Some(new)) = (old.as_array(), new.as_array()) {
+        let old = old
+            .iter()
+            .map(|v| Item::Value(v.clone()))
+            .collect::<Vec<_>>();
+        let new = new
+            .iter()
+            .map(|v| Item::Value(v.clone()))
+            .collect::<Vec<_>>();
+        diff_elements(&old, &new, path, changes);
+    } else if !scalar_eq(old, new) {
+        changes.push(Change::Modified {
+            path: path.clone(),
+            old: old.clone(),
+            new: new.clone(),
+        });
+    }
+}
+
+fn diff_elements(old: &[Item], new: &[Item], path: &mut Vec<String>, changes: &mut Vec<Change>) {
+    for i in 0..old.len().max(new.len()) {
+        path.push(i.to_string());
+        match (old.get(i), new.get(i)) {
+            (Some(old), Some(new)) => diff_item(old, new, path, changes),
+            (Some(old), None) => changes.push(Change::Removed {
+                path: path.clone(),
+                old: old.clone(),
+            }),
+            (None, Some(new)) => changes.push(Change::Added {
+                path: path.clone(),
+                new: new.clone(),
+            }),
+            (None, None) => unreachable!("index is within one of the arrays"),
+        }
+        path.pop();
+    }
+}
+
+/// Compares non-container values, ignoring their formatting
+fn scalar_eq(old: &Item, new: &Item) -> bool {
+    match (old.as_value(), new.as_value()) {
+        (Some(old), Some(new)) => old == new,
+        _ => old.is_none() && new.is_none(),
+    }
+}
+
 impl Default for Document {
     fn default() -> Self {
         Self {
@@ -73,6 +1436,8 @@ impl Default for Document {
             trailing: Default::default(),
             original: Default::default(),
             span: Default::default(),
+            line_ending: Default::default(),
+            bom: false,
         }
     }
 }
@@ -89,7 +1454,7 @@ impl FromStr for Document {
     }
 }
 
-impl std::ops::Deref for Document {
+impl core::ops::Deref for Document {
     type Target = Table;
 
     fn deref(&self) -> &Self::Target {
@@ -97,7 +1462,7 @@ impl std::ops::Deref for Document {
     }
 }
 
-impl std::ops::DerefMut for Document {
+impl core::ops::DerefMut for Document {
     fn deref_mut(&mut self) -> &mut Self::Target {
         self.as_table_mut()
     }
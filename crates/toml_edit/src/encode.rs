@@ -1,11 +1,12 @@
-use std::borrow::Cow;
-use std::fmt::{Display, Formatter, Result, Write};
+use alloc::borrow::Cow;
+use core::fmt::{Display, Formatter, Result, Write};
 
 use toml_datetime::*;
 
-use crate::document::Document;
+use crate::document::{Document, LineEnding};
 use crate::inline_table::DEFAULT_INLINE_KEY_DECOR;
 use crate::key::Key;
+use crate::prelude::*;
 use crate::repr::{Formatted, Repr, ValueRepr};
 use crate::table::{DEFAULT_KEY_DECOR, DEFAULT_KEY_PATH_DECOR, DEFAULT_TABLE_DECOR};
 use crate::value::{
@@ -150,17 +151,34 @@ impl Encode for Array {
         decor.prefix_encode(buf, input, default_decor.0)?;
         write!(buf, "[")?;
 
+        let mut comma_written = false;
         for (i, elem) in self.iter().enumerate() {
             let inner_decor;
             if i == 0 {
                 inner_decor = DEFAULT_LEADING_VALUE_DECOR;
             } else {
                 inner_decor = DEFAULT_VALUE_DECOR;
-                write!(buf, ",")?;
+                if !comma_written {
+                    write!(buf, ",")?;
+                }
+            }
+            comma_written = false;
+            match open_comment(elem) {
+                // A comment would swallow the comma, so end it on the element's line
+                Some(comment) => {
+                    let mut bare = elem.clone();
+                    bare.decor_mut().set_suffix("");
+                    bare.encode(buf, input, inner_decor)?;
+                    if i + 1 < self.len() || self.trailing_comma() {
+                        write!(buf, ",")?;
+                        comma_written = true;
+                    }
+                    writeln!(buf, "{}", comment)?;
+                }
+                None => elem.encode(buf, input, inner_decor)?,
             }
-            elem.encode(buf, input, inner_decor)?;
         }
-        if self.trailing_comma() && !self.is_empty() {
+        if self.trailing_comma() && !self.is_empty() && !comma_written {
             write!(buf, ",")?;
         }
 
@@ -172,6 +190,14 @@ impl Encode for Array {
     }
 }
 
+/// The suffix of an array element if it ends with a comment not yet terminated by a newline,
+/// like one from [`Value::with_comment`]
+fn open_comment(value: &Value) -> Option<&str> {
+    let suffix = value.decor().suffix()?.as_str()?;
+    let last_line = suffix.rsplit('\n').next().unwrap_or_default();
+    last_line.contains('#').then_some(suffix)
+}
+
 impl Encode for InlineTable {
     fn encode(
         &self,
@@ -230,32 +256,61 @@ impl Encode for Value {
 
 impl Display for Document {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let mut path = Vec::new();
-        let mut last_position = 0;
-        let mut tables = Vec::new();
-        visit_nested_tables(self.as_table(), &mut path, false, &mut |t, p, is_array| {
-            if let Some(pos) = t.position() {
-                last_position = pos;
-            }
-            tables.push((last_position, t, p.clone(), is_array));
-            Ok(())
-        })
-        .unwrap();
-
-        tables.sort_by_key(|&(id, _, _, _)| id);
-        let mut first_table = true;
-        for (_, table, path, is_array) in tables {
-            visit_table(
-                f,
-                self.original.as_deref(),
-                table,
-                &path,
-                is_array,
-                &mut first_table,
-            )?;
+        match self.line_ending() {
+            LineEnding::Lf => encode_document(self, f),
+            LineEnding::Crlf => encode_document(self, &mut CrlfWriter(f)),
+        }
+    }
+}
+
+fn encode_document(doc: &Document, buf: &mut dyn Write) -> Result {
+    if doc.bom {
+        buf.write_char('\u{FEFF}')?;
+    }
+    let mut path = Vec::new();
+    let mut last_position = 0;
+    let mut tables = Vec::new();
+    visit_nested_tables(doc.as_table(), &mut path, false, &mut |t, p, is_array| {
+        if let Some(pos) = t.position() {
+            last_position = pos;
         }
-        self.trailing()
-            .encode_with_default(f, self.original.as_deref(), "")
+        tables.push((last_position, t, p.clone(), is_array));
+        Ok(())
+    })
+    .unwrap();
+
+    tables.sort_by_key(|&(id, _, _, _)| id);
+    let mut first_table = true;
+    for (_, table, path, is_array) in tables {
+        visit_table(
+            buf,
+            doc.original.as_deref(),
+            table,
+            &path,
+            is_array,
+            &mut first_table,
+        )?;
+    }
+    doc.trailing()
+        .encode_with_default(buf, doc.original.as_deref(), "")
+}
+
+/// Translates `\n` to `\r\n`
+///
+/// `RawString`s have their `\r` stripped when encoded, so this produces consistent line endings.
+struct CrlfWriter<'w>(&'w mut dyn Write);
+
+impl<'w> Write for CrlfWriter<'w> {
+    fn write_str(&mut self, s: &str) -> Result {
+        let mut lines = s.split('\n');
+        if let Some(first) = lines.next() {
+            self.0.write_str(first)?;
+        }
+        for line in lines {
+            self.0.write_str("\r\n")?;
+            self.0.write_str(line)?;
+        }
+        Ok(())
     }
 }
 
@@ -360,6 +415,18 @@ fn visit_table(
     Ok(())
 }
 
+pub(crate) fn to_datetime_repr(value: &Datetime, format: crate::DatetimeFormat) -> Repr {
+    let mut output = value.to_string();
+    if format.space_separator && value.date.is_some() && value.time.is_some() {
+        output.replace_range(10..11, " ");
+    }
+    if format.lowercase_z && value.offset == Some(Offset::Z) {
+        output.pop();
+        output.push('z');
+    }
+    Repr::new_unchecked(output)
+}
+
 impl ValueRepr for String {
     fn to_repr(&self) -> Repr {
         to_string_repr(self, None, None)
@@ -373,9 +440,9 @@ pub(crate) fn to_string_repr(
 ) -> Repr {
     let (style, literal) = match (style, literal) {
         (Some(style), Some(literal)) => (style, literal),
-        (_, Some(literal)) => (infer_style(value).0, literal),
-        (Some(style), _) => (style, infer_style(value).1),
-        (_, _) => infer_style(value),
+        (_, Some(literal)) => (infer_style(value, false).0, literal),
+        (Some(style), _) => (style, infer_style(value, false).1),
+        (_, _) => infer_style(value, false),
     };
 
     let mut output = String::with_capacity(value.len() * 2);
@@ -410,6 +477,34 @@ pub(crate) fn to_string_repr(
     Repr::new_unchecked(output)
 }
 
+/// Represent `value` with as few escapes as possible, which also avoids escaping `"`
+pub(crate) fn to_minimal_string_repr(value: &str) -> Repr {
+    let (style, literal) = infer_style(value, true);
+    to_string_repr(value, Some(style), Some(literal))
+}
+
+/// Represent `value` as a literal string, if possible
+pub(crate) fn to_literal_string_repr(value: &str) -> Option<Repr> {
+    let has_control = value
+        .chars()
+        .any(|c| (c <= '\u{1f}' && c != '\t' && c != '\n') || c == '\u{7f}');
+    if has_control || value.contains("'''") {
+        return None;
+    }
+    let style = if value.contains('\n') {
+        StringStyle::NewlineTriple
+    } else if value.contains('\'') {
+        StringStyle::OnelineTriple
+    } else {
+        StringStyle::OnelineSingle
+    };
+    if style != StringStyle::OnelineSingle && value.ends_with('\'') {
+        // We cannot escape the ending quote
+        return None;
+    }
+    Some(to_string_repr(value, Some(style), Some(true)))
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) enum StringStyle {
     NewlineTriple,
@@ -454,7 +549,7 @@ impl StringStyle {
     }
 }
 
-fn infer_style(value: &str) -> (StringStyle, bool) {
+fn infer_style(value: &str, avoid_quote_escapes: bool) -> (StringStyle, bool) {
     // We need to determine:
     // - if we are a "multi-line" pretty (if there are \n)
     // - if ['''] appears if multi or ['] if single
@@ -468,6 +563,8 @@ fn infer_style(value: &str) -> (StringStyle, bool) {
     let mut found_singles = 0;
     let mut prefer_literal = false;
     let mut can_be_pretty = true;
+    // `"` only needs escaping in a basic string, but `'` would force `'''`
+    let has_single = value.contains('\'');
 
     for ch in value.chars() {
         if can_be_pretty {
@@ -487,6 +584,9 @@ fn infer_style(value: &str) -> (StringStyle, bool) {
                 '\\' => {
                     prefer_literal = true;
                 }
+                '"' if avoid_quote_escapes && !has_single => {
+                    prefer_literal = true;
+                }
                 '\n' => ty = StringStyle::NewlineTriple,
                 // Escape codes are needed if any ascii control
                 // characters are present, including \b \f \r.
@@ -1,5 +1,6 @@
-use std::error::Error as StdError;
-use std::fmt::{Display, Formatter, Result};
+use core::fmt::{Display, Formatter, Result};
+
+use crate::prelude::*;
 
 /// Type representing a TOML parse error
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -7,7 +8,7 @@ pub struct TomlError {
     message: String,
     original: Option<String>,
     keys: Vec<String>,
-    span: Option<std::ops::Range<usize>>,
+    span: Option<core::ops::Range<usize>>,
 }
 
 impl TomlError {
@@ -41,8 +42,20 @@ impl TomlError {
         }
     }
 
+    /// Report the error against a different copy of the document
+    #[cfg(feature = "parse")]
+    pub(crate) fn with_original(
+        mut self,
+        original: &str,
+        span: Option<core::ops::Range<usize>>,
+    ) -> Self {
+        self.original = Some(original.to_owned());
+        self.span = span;
+        self
+    }
+
     #[cfg(feature = "serde")]
-    pub(crate) fn custom(message: String, span: Option<std::ops::Range<usize>>) -> Self {
+    pub(crate) fn custom(message: String, span: Option<core::ops::Range<usize>>) -> Self {
         Self {
             message,
             original: None,
@@ -62,12 +75,12 @@ impl TomlError {
     }
 
     /// The start/end index into the original document where the error occurred
-    pub fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub fn span(&self) -> Option<core::ops::Range<usize>> {
         self.span.clone()
     }
 
     #[cfg(feature = "serde")]
-    pub(crate) fn set_span(&mut self, span: Option<std::ops::Range<usize>>) {
+    pub(crate) fn set_span(&mut self, span: Option<core::ops::Range<usize>>) {
         self.span = span;
     }
 
@@ -141,12 +154,46 @@ impl Display for TomlError {
     }
 }
 
-impl StdError for TomlError {
+#[cfg(feature = "std")]
+impl std::error::Error for TomlError {
     fn description(&self) -> &'static str {
         "TOML parse error"
     }
 }
 
+/// A construct that is valid TOML but discouraged, see
+/// [`Document::parse_with_warnings`][crate::Document::parse_with_warnings]
+#[cfg(feature = "parse")]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Warning {
+    message: String,
+    span: Option<core::ops::Range<usize>>,
+}
+
+#[cfg(feature = "parse")]
+impl Warning {
+    pub(crate) fn new(message: String, span: Option<core::ops::Range<usize>>) -> Self {
+        Self { message, span }
+    }
+
+    /// What was flagged
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The start/end index into the original document of the flagged construct
+    pub fn span(&self) -> Option<core::ops::Range<usize>> {
+        self.span.clone()
+    }
+}
+
+#[cfg(feature = "parse")]
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.message.fmt(f)
+    }
+}
+
 fn translate_position(input: &[u8], index: usize) -> (usize, usize) {
     if input.is_empty() {
         return (0, index);
@@ -168,7 +215,7 @@ fn translate_position(input: &[u8], index: usize) -> (usize, usize) {
     };
     let line = input[0..line_start].iter().filter(|b| **b == b'\n').count();
 
-    let column = std::str::from_utf8(&input[line_start..=index])
+    let column = core::str::from_utf8(&input[line_start..=index])
         .map(|s| s.chars().count() - 1)
         .unwrap_or_else(|_| index - line_start);
     let column = column + column_offset;
@@ -1,7 +1,8 @@
-use std::ops;
+use core::ops;
 
 use crate::document::Document;
 use crate::key::Key;
+use crate::prelude::*;
 use crate::table::TableKeyValue;
 use crate::{value, InlineTable, InternalString, Item, Table, Value};
 
@@ -1,8 +1,11 @@
-use std::iter::FromIterator;
+use core::iter::FromIterator;
 
 use crate::key::Key;
+use crate::prelude::*;
 use crate::repr::Decor;
-use crate::table::{Iter, IterMut, KeyValuePairs, TableKeyValue, TableLike};
+use crate::table::{
+    Iter, IterMut, KeyValuePairs, MergePolicy, MergeStrategy, TableKeyValue, TableLike,
+};
 use crate::{InternalString, Item, KeyMut, RawString, Table, Value};
 
 /// Type representing a TOML inline table,
@@ -15,7 +18,7 @@ pub struct InlineTable {
     pub(crate) implicit: bool,
     // prefix before `{` and suffix after `}`
     decor: Decor,
-    pub(crate) span: Option<std::ops::Range<usize>>,
+    pub(crate) span: Option<core::ops::Range<usize>>,
     // whether this is a proxy for dotted keys
     dotted: bool,
     pub(crate) items: KeyValuePairs,
@@ -82,6 +85,40 @@ impl InlineTable {
         decorate_inline_table(self);
     }
 
+    /// Set whether to pad the inside of the braces, `{a = 1}` when compact instead of `{ a = 1 }`
+    ///
+    /// Empty tables are left as-is.
+    pub fn set_compact(&mut self, yes: bool) {
+        let padding = if yes { "" } else { " " };
+        if let Some(key) = self.first_key_mut() {
+            key.decor_mut().set_prefix(padding);
+        }
+        if let Some(value) = self.last_value_mut() {
+            value.decor_mut().set_suffix(padding);
+        }
+    }
+
+    fn first_key_mut(&mut self) -> Option<&mut Key> {
+        self.items
+            .values_mut()
+            .find(|kv| kv.value.is_value())
+            .map(|kv| &mut kv.key)
+    }
+
+    fn last_value_mut(&mut self) -> Option<&mut Value> {
+        let kv = self
+            .items
+            .values_mut()
+            .rev()
+            .find(|kv| kv.value.is_value())?;
+        let value = kv.value.as_value_mut()?;
+        if value.as_inline_table().map_or(false, |t| t.is_dotted()) {
+            value.as_inline_table_mut()?.last_value_mut()
+        } else {
+            Some(value)
+        }
+    }
+
     /// Sorts the key/value pairs by key.
     pub fn sort_values(&mut self) {
         // Assuming standard tables have their position set and this won't negatively impact them
@@ -102,25 +139,25 @@ impl InlineTable {
     /// values or their combination as needed).
     pub fn sort_values_by<F>(&mut self, mut compare: F)
     where
-        F: FnMut(&Key, &Value, &Key, &Value) -> std::cmp::Ordering,
+        F: FnMut(&Key, &Value, &Key, &Value) -> core::cmp::Ordering,
     {
         self.sort_values_by_internal(&mut compare);
     }
 
     fn sort_values_by_internal<F>(&mut self, compare: &mut F)
     where
-        F: FnMut(&Key, &Value, &Key, &Value) -> std::cmp::Ordering,
+        F: FnMut(&Key, &Value, &Key, &Value) -> core::cmp::Ordering,
     {
         let modified_cmp = |_: &InternalString,
                             val1: &TableKeyValue,
                             _: &InternalString,
                             val2: &TableKeyValue|
-         -> std::cmp::Ordering {
+         -> core::cmp::Ordering {
             match (val1.value.as_value(), val2.value.as_value()) {
                 (Some(v1), Some(v2)) => compare(&val1.key, v1, &val2.key, v2),
-                (Some(_), None) => std::cmp::Ordering::Greater,
-                (None, Some(_)) => std::cmp::Ordering::Less,
-                (None, None) => std::cmp::Ordering::Equal,
+                (Some(_), None) => core::cmp::Ordering::Greater,
+                (None, Some(_)) => core::cmp::Ordering::Less,
+                (None, None) => core::cmp::Ordering::Equal,
             }
         };
 
@@ -206,7 +243,7 @@ impl InlineTable {
     }
 
     /// Returns the location within the original document
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         self.span.clone()
     }
 
@@ -223,6 +260,16 @@ impl InlineTable {
 
 impl InlineTable {
     /// Returns an iterator over key/value pairs.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let value = "{ a = 1, b = 2 }".parse::<toml_edit::Value>().unwrap();
+    /// let table = value.as_inline_table().unwrap();
+    /// let keys = table.iter().map(|(k, _)| k).collect::<Vec<_>>();
+    /// assert_eq!(keys, ["a", "b"]);
+    /// # }
+    /// ```
     pub fn iter(&self) -> InlineTableIter<'_> {
         Box::new(
             self.items
@@ -232,7 +279,20 @@ impl InlineTable {
         )
     }
 
-    /// Returns an iterator over key/value pairs.
+    /// Returns an iterator over key/value pairs, with mutable access to each value and key.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut value = "{ a = 1, b = 2 }".parse::<toml_edit::Value>().unwrap();
+    /// let table = value.as_inline_table_mut().unwrap();
+    /// for (_, v) in table.iter_mut() {
+    ///     *v = toml_edit::Value::from(v.as_integer().unwrap() * 2);
+    /// }
+    /// table.fmt();
+    /// assert_eq!(value.to_string(), "{ a = 2, b = 4 }");
+    /// # }
+    /// ```
     pub fn iter_mut(&mut self) -> InlineTableIterMut<'_> {
         Box::new(
             self.items
@@ -242,6 +302,66 @@ impl InlineTable {
         )
     }
 
+    /// Copies the key/value pairs into a map, dropping each value's decor
+    pub fn to_map(&self) -> alloc::collections::BTreeMap<String, Value> {
+        self.iter()
+            .map(|(k, v)| (k.to_owned(), v.clone_bare()))
+            .collect()
+    }
+
+    /// Merges the key/value pairs of `other` into this table.
+    ///
+    /// Inline tables present in both are merged recursively, while any other value present in
+    /// both is overwritten, keeping the existing decor.  Keys only in `other` are appended.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut defaults = "{ a = 1, opt = { x = 1 } }".parse::<toml_edit::Value>().unwrap();
+    /// let overrides = "{ opt = { y = 2 }, b = 3 }".parse::<toml_edit::Value>().unwrap();
+    /// let table = defaults.as_inline_table_mut().unwrap();
+    /// table.merge(overrides.as_inline_table().unwrap());
+    /// assert_eq!(defaults.to_string(), "{ a = 1, opt = { x = 1, y = 2 }, b = 3 }");
+    /// # }
+    /// ```
+    pub fn merge(&mut self, other: &InlineTable) {
+        self.merge_table_like(other, MergePolicy::Strategy(MergeStrategy::Override));
+    }
+
+    pub(crate) fn merge_table_like(&mut self, other: &dyn TableLike, policy: MergePolicy) {
+        // The whitespace before the closing brace moves to the new last value
+        let padding = self
+            .last_value_mut()
+            .and_then(|value| value.decor().suffix().cloned());
+        let mut appended = false;
+        for (key, item) in other.iter() {
+            match self.items.get_mut(key) {
+                Some(kv) => {
+                    let decor = kv.value.as_value().map(|value| value.decor().clone());
+                    crate::table::merge_item(&mut kv.value, item, policy, true);
+                    if let (Some(decor), Some(value)) = (decor, kv.value.as_value_mut()) {
+                        *value.decor_mut() = decor;
+                    }
+                }
+                None => {
+                    if let Ok(mut value) = item.clone().into_value() {
+                        if !appended {
+                            if let Some(last) = self.last_value_mut() {
+                                last.decor_mut().set_suffix("");
+                            }
+                            appended = true;
+                        }
+                        value.decor_mut().clear();
+                        self.insert(key, value);
+                    }
+                }
+            }
+        }
+        if let (true, Some(padding), Some(last)) = (appended, padding, self.last_value_mut()) {
+            last.decor_mut().set_suffix(padding);
+        }
+    }
+
     /// Returns the number of key/value pairs.
     pub fn len(&self) -> usize {
         self.iter().count()
@@ -262,7 +382,7 @@ impl InlineTable {
         match self.items.entry(key.into()) {
             indexmap::map::Entry::Occupied(mut entry) => {
                 // Ensure it is a `Value` to simplify `InlineOccupiedEntry`'s code.
-                let scratch = std::mem::take(&mut entry.get_mut().value);
+                let scratch = core::mem::take(&mut entry.get_mut().value);
                 let scratch = Item::Value(
                     scratch
                         .into_value()
@@ -286,7 +406,7 @@ impl InlineTable {
         match self.items.entry(key.get().into()) {
             indexmap::map::Entry::Occupied(mut entry) => {
                 // Ensure it is a `Value` to simplify `InlineOccupiedEntry`'s code.
-                let scratch = std::mem::take(&mut entry.get_mut().value);
+                let scratch = core::mem::take(&mut entry.get_mut().value);
                 let scratch = Item::Value(
                     scratch
                         .into_value()
@@ -316,6 +436,31 @@ impl InlineTable {
             .and_then(|kv| kv.value.as_value_mut())
     }
 
+    /// Return an optional reference to the value at `path`, descending through nested inline
+    /// tables.
+    ///
+    /// Dotted keys like `{ a.b = 1 }` are nested tables too, so `["a", "b"]` finds `1`.  An empty
+    /// `path` returns `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let value = "{ a = { b = { c = 1 } } }".parse::<toml_edit::Value>().unwrap();
+    /// let table = value.as_inline_table().unwrap();
+    /// assert_eq!(table.get_path(&["a", "b", "c"]).and_then(|v| v.as_integer()), Some(1));
+    /// assert!(table.get_path(&["a", "x"]).is_none());
+    /// # }
+    /// ```
+    pub fn get_path(&self, path: &[&str]) -> Option<&Value> {
+        let (last, parents) = path.split_last()?;
+        let mut table = self;
+        for key in parents {
+            table = table.get(key)?.as_inline_table()?;
+        }
+        table.get(last)
+    }
+
     /// Return references to the key-value pair stored for key, if it is present, else None.
     pub fn get_key_value<'a>(&'a self, key: &str) -> Option<(&'a Key, &'a Item)> {
         self.items.get(key).and_then(|kv| {
@@ -416,8 +561,8 @@ impl InlineTable {
 }
 
 #[cfg(feature = "display")]
-impl std::fmt::Display for InlineTable {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for InlineTable {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, None, ("", ""))
     }
 }
@@ -601,6 +746,18 @@ impl<'a> InlineEntry<'a> {
         }
     }
 
+    /// Provides in-place mutable access to an occupied entry before any potential inserts
+    /// into the map.
+    pub fn and_modify<F: FnOnce(&mut Value)>(self, f: F) -> Self {
+        match self {
+            InlineEntry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                InlineEntry::Occupied(entry)
+            }
+            InlineEntry::Vacant(entry) => InlineEntry::Vacant(entry),
+        }
+    }
+
     /// Ensures a value is in the entry by inserting the default if empty, and returns
     /// a mutable reference to the value in the entry.
     pub fn or_insert(self, default: Value) -> &'a mut Value {
@@ -665,7 +822,7 @@ impl<'a> InlineOccupiedEntry<'a> {
     /// Sets the value of the entry, and returns the entry's old value
     pub fn insert(&mut self, value: Value) -> Value {
         let mut value = Item::Value(value);
-        std::mem::swap(&mut value, &mut self.entry.get_mut().value);
+        core::mem::swap(&mut value, &mut self.entry.get_mut().value);
         value.into_value().unwrap()
     }
 
@@ -1,9 +1,10 @@
-use std::str::FromStr;
+use core::str::FromStr;
 
 use toml_datetime::*;
 
 use crate::array_of_tables::ArrayOfTables;
-use crate::table::TableLike;
+use crate::prelude::*;
+use crate::table::{MergePolicy, TableLike};
 use crate::{Array, InlineTable, Table, Value};
 
 /// Type representing either a value, a table, an array of tables, or none.
@@ -29,6 +30,142 @@ impl Item {
         }
         self
     }
+
+    /// Sets `self` to an empty array iff `self` is none and
+    /// returns a mutable reference to the array.
+    ///
+    /// # Panics
+    ///
+    /// If `self` is something other than none or an array.
+    pub fn or_insert_array(&mut self) -> &mut Array {
+        let item = self.or_insert(Item::Value(Value::Array(Array::new())));
+        let type_name = item.type_name();
+        item.as_array_mut()
+            .unwrap_or_else(|| panic!("expected an array, found {}", type_name))
+    }
+
+    /// Merges `other` into `self`.
+    ///
+    /// Tables and inline tables are merged key by key, recursively, and arrays of tables are
+    /// concatenated.  Anything else in `other`, including an array, overwrites `self`.
+    ///
+    /// See also [`Table::merge_with`] for other policies.
+    pub fn merge(&mut self, other: &Item) {
+        crate::table::merge_item(self, other, MergePolicy::AppendArraysOfTables, false);
+    }
+
+    /// Calls `f` on every value in the subtree, along with the path to it.
+    ///
+    /// Arrays and inline tables are visited before their contents.  Array elements and arrays of
+    /// tables use the element's index as the path segment.
+    ///
+    /// For more control, see [`crate::visit_mut`].
+    pub fn walk_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&[&str], &mut Value),
+    {
+        let mut path = Vec::new();
+        walk_item_mut(self, &mut path, &mut f);
+    }
+
+    /// The deepest nesting of tables and arrays within the item
+    ///
+    /// Scalars have a depth of `0`.  Tables, arrays, and inline tables each add a level; an array
+    /// of tables and its tables add one level together.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let value = "{ a = { b = 1 } }".parse::<toml_edit::Value>().unwrap();
+    /// assert_eq!(toml_edit::Item::Value(value).max_depth(), 2);
+    /// # }
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        match self {
+            Item::None => 0,
+            Item::Value(value) => value_depth(value),
+            Item::Table(table) => table_depth(table),
+            Item::ArrayOfTables(array) => array.iter().map(table_depth).max().unwrap_or(1),
+        }
+    }
+}
+
+fn table_depth(table: &Table) -> usize {
+    1 + table
+        .iter()
+        .map(|(_, item)| item.max_depth())
+        .max()
+        .unwrap_or(0)
+}
+
+fn value_depth(value: &Value) -> usize {
+    match value {
+        Value::Array(array) => 1 + array.iter().map(value_depth).max().unwrap_or(0),
+        Value::InlineTable(table) => {
+            1 + table
+                .iter()
+                .map(|(_, value)| value_depth(value))
+                .max()
+                .unwrap_or(0)
+        }
+        _ => 0,
+    }
+}
+
+fn walk_item_mut<F>(item: &mut Item, path: &mut Vec<String>, f: &mut F)
+where
+    F: FnMut(&[&str], &mut Value),
+{
+    match item {
+        Item::None => {}
+        Item::Value(value) => walk_value_mut(value, path, f),
+        Item::Table(table) => walk_table_mut(table, path, f),
+        Item::ArrayOfTables(array) => {
+            for (i, table) in array.iter_mut().enumerate() {
+                path.push(i.to_string());
+                walk_table_mut(table, path, f);
+                path.pop();
+            }
+        }
+    }
+}
+
+fn walk_table_mut<F>(table: &mut Table, path: &mut Vec<String>, f: &mut F)
+where
+    F: FnMut(&[&str], &mut Value),
+{
+    for (key, item) in table.iter_mut() {
+        path.push(key.get().to_owned());
+        walk_item_mut(item, path, f);
+        path.pop();
+    }
+}
+
+fn walk_value_mut<F>(value: &mut Value, path: &mut Vec<String>, f: &mut F)
+where
+    F: FnMut(&[&str], &mut Value),
+{
+    {
+        let segments = path.iter().map(String::as_str).collect::<Vec<_>>();
+        f(&segments, value);
+    }
+    match value {
+        Value::Array(array) => {
+            for (i, value) in array.iter_mut().enumerate() {
+                path.push(i.to_string());
+                walk_value_mut(value, path, f);
+                path.pop();
+            }
+        }
+        Value::InlineTable(table) => {
+            for (key, value) in table.iter_mut() {
+                path.push(key.get().to_owned());
+                walk_value_mut(value, path, f);
+                path.pop();
+            }
+        }
+        _ => {}
+    }
 }
 
 // TODO: This should be generated by macro or derive
@@ -44,6 +181,30 @@ impl Item {
         }
     }
 
+    /// Compact description of the content, without formatting
+    ///
+    /// Containers report their size, like `Table{3 keys}`, while scalars include their value, like
+    /// `Value(Integer 42)`.
+    pub fn summary(&self) -> String {
+        match self {
+            Item::None => "None".to_owned(),
+            Item::Value(v) => {
+                let content = match v {
+                    Value::String(s) => format!("String {:?}", s.value()),
+                    Value::Integer(i) => format!("Integer {}", i.value()),
+                    Value::Float(f) => format!("Float {}", f.value()),
+                    Value::Boolean(b) => format!("Boolean {}", b.value()),
+                    Value::Datetime(d) => format!("Datetime {}", d.value()),
+                    Value::Array(a) => format!("Array{{{} items}}", a.len()),
+                    Value::InlineTable(t) => format!("InlineTable{{{} keys}}", t.len()),
+                };
+                format!("Value({})", content)
+            }
+            Item::Table(t) => format!("Table{{{} keys}}", t.len()),
+            Item::ArrayOfTables(a) => format!("ArrayOfTables{{{} tables}}", a.len()),
+        }
+    }
+
     /// Index into a TOML array or map. A string index can be used to access a
     /// value in a map, and a usize index can be used to access an element of an
     /// array.
@@ -131,7 +292,7 @@ impl Item {
     }
     /// In-place convert to a value
     pub fn make_value(&mut self) {
-        let other = std::mem::take(self);
+        let other = core::mem::take(self);
         let other = other.into_value().map(Item::Value).unwrap_or(Item::None);
         *self = other;
     }
@@ -166,16 +327,15 @@ impl Item {
     }
     // Starting private because the name is unclear
     pub(crate) fn make_item(&mut self) {
-        let other = std::mem::take(self);
+        let other = core::mem::take(self);
         let other = match other.into_table().map(crate::Item::Table) {
             Ok(i) => i,
             Err(i) => i,
         };
-        let other =
-            match other.into_array_of_tables().map(crate::Item::ArrayOfTables) {
-                Ok(i) => i,
-                Err(i) => i,
-            };
+        let other = match other.into_array_of_tables().map(crate::Item::ArrayOfTables) {
+            Ok(i) => i,
+            Err(i) => i,
+        };
         *self = other;
     }
     /// Returns true iff `self` is a value.
@@ -187,6 +347,9 @@ impl Item {
         self.as_table().is_some()
     }
     /// Returns true iff `self` is an array of tables.
+    ///
+    /// This is only the `[[x]]` header spelling; an inline `x = [{ a = 1 }]` is a
+    /// [`Value::Array`], see [`Value::is_array_of_inline_tables`].
     pub fn is_array_of_tables(&self) -> bool {
         self.as_array_of_tables().is_some()
     }
@@ -302,7 +465,7 @@ impl Item {
     }
 
     /// Returns the location within the original document
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         match self {
             Item::None => None,
             Item::Value(v) => v.span(),
@@ -344,9 +507,23 @@ impl FromStr for Item {
     }
 }
 
+impl<V: Into<Value>> From<alloc::collections::BTreeMap<String, V>> for Item {
+    /// Creates a table, with keys in sorted order
+    fn from(map: alloc::collections::BTreeMap<String, V>) -> Self {
+        Item::Table(map.into_iter().collect())
+    }
+}
+
+impl<V: Into<Value>> From<Vec<V>> for Item {
+    /// Creates an array value
+    fn from(values: Vec<V>) -> Self {
+        Item::Value(Value::Array(values.into_iter().collect()))
+    }
+}
+
 #[cfg(feature = "display")]
-impl std::fmt::Display for Item {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Item {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self {
             Item::None => Ok(()),
             Item::Value(v) => v.fmt(f),
@@ -1,6 +1,7 @@
-use std::borrow::Cow;
-use std::str::FromStr;
+use alloc::borrow::Cow;
+use core::str::FromStr;
 
+use crate::prelude::*;
 use crate::repr::{Decor, Repr};
 use crate::InternalString;
 
@@ -25,7 +26,19 @@ use crate::InternalString;
 ///
 /// For details see [toml spec](https://github.com/toml-lang/toml/#keyvalue-pair).
 ///
+/// Bare keys may only contain ASCII letters, ASCII digits, `_`, and `-`.  Any other key,
+/// including one with non-ASCII characters like `"café"`, is quoted when no representation was
+/// parsed for it.
+///
 /// To parse a key use `FromStr` trait implementation: `"string".parse::<Key>()`.
+///
+/// # Dotted keys
+///
+/// A `Key` is always a single segment: `a.b.c = 1` holds the three keys `a`, `b` and `c`, as
+/// does the header `[a.b.c]`.  A dotted key is handled as a path of `Key`s instead, built with
+/// [`Key::path`] or parsed with `Key::parse`, so there is no segment iteration on `Key` itself,
+/// and [`Table::insert`][crate::Table::insert] treats `"a.b"` as one key.  Insert under a path
+/// with [`Table::insert_dotted`][crate::Table::insert_dotted].
 #[derive(Debug)]
 pub struct Key {
     key: InternalString,
@@ -43,9 +56,26 @@ impl Key {
         }
     }
 
+    /// Create the path of a dotted key, like `a.b.c`, with one `Key` per segment
+    ///
+    /// See [dotted keys](Key#dotted-keys).  Each segment is a whole key, so a `.` within a
+    /// segment is quoted when rendered.
+    pub fn path(segments: &[&str]) -> Vec<Self> {
+        segments.iter().map(|segment| Self::new(*segment)).collect()
+    }
+
     /// Parse a TOML key expression
     ///
-    /// Unlike `"".parse<Key>()`, this supports dotted keys.
+    /// Unlike `"".parse<Key>()`, this supports [dotted keys](Key#dotted-keys), returned as one
+    /// `Key` per segment, each unescaped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let keys = toml_edit::Key::parse(r#"a."b.c""#).unwrap();
+    /// let segments: Vec<_> = keys.iter().map(|k| k.get()).collect();
+    /// assert_eq!(segments, ["a", "b.c"]);
+    /// ```
     #[cfg(feature = "parse")]
     pub fn parse(repr: &str) -> Result<Vec<Self>, crate::TomlError> {
         Self::try_parse_path(repr)
@@ -81,6 +111,14 @@ impl Key {
         self.repr.as_ref()
     }
 
+    /// Returns the key exactly as it was written, including any quotes, if available.
+    ///
+    /// Surrounding whitespace is part of the [`Key::decor`] instead.  Keys created
+    /// programmatically have no raw form until one is set.
+    pub fn raw(&self) -> Option<&str> {
+        self.as_repr().and_then(|r| r.as_raw().as_str())
+    }
+
     /// Returns the default raw representation.
     #[cfg(feature = "display")]
     pub fn default_repr(&self) -> Repr {
@@ -98,6 +136,32 @@ impl Key {
             })
     }
 
+    #[cfg(feature = "display")]
+    pub(crate) fn set_quote_style(&mut self, style: KeyQuoteStyle) {
+        let can_be_bare = to_key_repr(&self.key).as_raw().as_str() == Some(self.key.as_str());
+        let is_bare = match self.as_repr() {
+            Some(repr) => repr.as_raw().as_str() == Some(self.key.as_str()),
+            None => can_be_bare,
+        };
+        match style {
+            KeyQuoteStyle::Preserve => {}
+            KeyQuoteStyle::AlwaysBare => {
+                if can_be_bare {
+                    self.repr = None;
+                }
+            }
+            KeyQuoteStyle::AlwaysQuoted => {
+                if is_bare {
+                    self.repr = Some(crate::encode::to_string_repr(
+                        &self.key,
+                        Some(crate::encode::StringStyle::OnelineSingle),
+                        Some(false),
+                    ));
+                }
+            }
+        }
+    }
+
     /// Returns the surrounding whitespace
     pub fn decor_mut(&mut self) -> &mut Decor {
         &mut self.decor
@@ -110,7 +174,7 @@ impl Key {
 
     /// Returns the location within the original document
     #[cfg(feature = "serde")]
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         self.repr.as_ref().and_then(|r| r.span())
     }
 
@@ -155,7 +219,7 @@ impl Clone for Key {
     }
 }
 
-impl std::ops::Deref for Key {
+impl core::ops::Deref for Key {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -163,20 +227,20 @@ impl std::ops::Deref for Key {
     }
 }
 
-impl std::hash::Hash for Key {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+impl core::hash::Hash for Key {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
         self.get().hash(state);
     }
 }
 
 impl Ord for Key {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
         self.get().cmp(other.get())
     }
 }
 
 impl PartialOrd for Key {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
         Some(self.cmp(other))
     }
 }
@@ -212,8 +276,8 @@ impl PartialEq<String> for Key {
 }
 
 #[cfg(feature = "display")]
-impl std::fmt::Display for Key {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Key {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, None, ("", ""))
     }
 }
@@ -234,13 +298,7 @@ impl FromStr for Key {
 fn to_key_repr(key: &str) -> Repr {
     #[cfg(feature = "parse")]
     {
-        if key
-            .as_bytes()
-            .iter()
-            .copied()
-            .all(crate::parser::key::is_unquoted_char)
-            && !key.is_empty()
-        {
+        if is_valid_bare_key(key) {
             Repr::new_unchecked(key)
         } else {
             crate::encode::to_string_repr(
@@ -291,6 +349,81 @@ impl From<Key> for InternalString {
     }
 }
 
+/// How keys are quoted, see [`Document::set_key_quote_style`][crate::Document::set_key_quote_style]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum KeyQuoteStyle {
+    /// Keep each key as it is
+    #[default]
+    Preserve,
+    /// Unquote keys that are valid bare keys
+    AlwaysBare,
+    /// Quote every bare key, e.g. `"key"`
+    AlwaysQuoted,
+}
+
+/// Whether `s` can be written as a key without quotes
+///
+/// Bare keys are non-empty and only contain ASCII letters, ASCII digits, `_` and `-`.
+///
+/// # Examples
+///
+/// ```rust
+/// assert!(toml_edit::is_valid_bare_key("server-1"));
+/// assert!(!toml_edit::is_valid_bare_key("a b"));
+/// assert!(!toml_edit::is_valid_bare_key("a.b"));
+/// assert!(!toml_edit::is_valid_bare_key(""));
+/// ```
+#[cfg(feature = "parse")]
+pub fn is_valid_bare_key(s: &str) -> bool {
+    !s.is_empty()
+        && s.as_bytes()
+            .iter()
+            .copied()
+            .all(crate::parser::key::is_unquoted_char)
+}
+
+/// Parses a single key, bare or quoted, without surrounding whitespace
+///
+/// Use [`Key::parse`] for dotted keys.
+///
+/// # Examples
+///
+/// ```rust
+/// let key = toml_edit::parse_key("'a b'").unwrap();
+/// assert_eq!(key.get(), "a b");
+/// assert!(toml_edit::parse_key("a b").is_err());
+/// assert!(toml_edit::parse_key("a.b").is_err());
+/// ```
+#[cfg(feature = "parse")]
+pub fn parse_key(s: &str) -> Result<Key, InvalidKey> {
+    Key::try_parse_simple(s).map_err(|inner| InvalidKey { inner })
+}
+
+/// A string is not a valid key, see [`parse_key`]
+#[cfg(feature = "parse")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidKey {
+    inner: crate::TomlError,
+}
+
+#[cfg(feature = "parse")]
+impl InvalidKey {
+    /// The location of the error within the string
+    pub fn span(&self) -> Option<core::ops::Range<usize>> {
+        self.inner.span()
+    }
+}
+
+#[cfg(feature = "parse")]
+impl core::fmt::Display for InvalidKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(all(feature = "parse", feature = "std"))]
+impl std::error::Error for InvalidKey {}
+
 /// A mutable reference to a `Key`
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct KeyMut<'k> {
@@ -308,12 +441,22 @@ impl<'k> KeyMut<'k> {
         self.key.as_repr()
     }
 
+    /// Returns the key exactly as it was written, including any quotes, if available.
+    pub fn raw(&self) -> Option<&str> {
+        self.key.raw()
+    }
+
     /// Returns the default raw representation.
     #[cfg(feature = "display")]
     pub fn default_repr(&self) -> Repr {
         self.key.default_repr()
     }
 
+    #[cfg(feature = "display")]
+    pub(crate) fn set_quote_style(&mut self, style: KeyQuoteStyle) {
+        self.key.set_quote_style(style)
+    }
+
     /// Returns a raw representation.
     #[cfg(feature = "display")]
     pub fn display_repr(&self) -> Cow<str> {
@@ -336,7 +479,7 @@ impl<'k> KeyMut<'k> {
     }
 }
 
-impl<'k> std::ops::Deref for KeyMut<'k> {
+impl<'k> core::ops::Deref for KeyMut<'k> {
     type Target = str;
 
     fn deref(&self) -> &Self::Target {
@@ -366,8 +509,8 @@ impl<'s> PartialEq<String> for KeyMut<'s> {
 }
 
 #[cfg(feature = "display")]
-impl<'k> std::fmt::Display for KeyMut<'k> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(&self.key, f)
+impl<'k> core::fmt::Display for KeyMut<'k> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(&self.key, f)
     }
 }
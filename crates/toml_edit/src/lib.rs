@@ -2,6 +2,7 @@
 // https://github.com/Marwes/combine/issues/172
 #![recursion_limit = "256"]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 //! # `toml_edit`
 //!
@@ -68,8 +69,17 @@
 //!
 //! * Order of dotted keys, see [issue](https://github.com/toml-rs/toml/issues/163).
 //!
+//! ## Feature flags
+//!
+//! The `std` feature is on by default.  Without it the crate is `no_std` and only needs
+//! `alloc`: parsing, editing and rendering work the same, while `std::error::Error` impls and
+//! [`Datetime::now_utc`] are unavailable, and table keys are hashed without a random seed.
+//! The `serde` and `perf` features require `std`.
+//!
 //! [`toml`]: https://docs.rs/toml/latest/toml/
 
+extern crate alloc;
+
 mod array;
 mod array_of_tables;
 mod document;
@@ -87,6 +97,8 @@ mod raw_string;
 mod repr;
 mod table;
 mod value;
+#[cfg(feature = "display")]
+mod writer;
 
 #[cfg(feature = "serde")]
 pub mod de;
@@ -100,29 +112,46 @@ pub use crate::array::{Array, ArrayIntoIter, ArrayIter, ArrayIterMut};
 pub use crate::array_of_tables::{
     ArrayOfTables, ArrayOfTablesIntoIter, ArrayOfTablesIter, ArrayOfTablesIterMut,
 };
-pub use crate::document::Document;
+#[cfg(feature = "parse")]
+pub use crate::document::ParseOptions;
+pub use crate::document::{Change, Document, DocumentSnapshot, DocumentStats, LineEnding};
 pub use crate::error::TomlError;
+#[cfg(feature = "parse")]
+pub use crate::error::Warning;
 pub use crate::inline_table::{
     InlineEntry, InlineOccupiedEntry, InlineTable, InlineTableIntoIter, InlineTableIter,
     InlineTableIterMut, InlineVacantEntry,
 };
 pub use crate::internal_string::InternalString;
 pub use crate::item::{array, table, value, Item};
-pub use crate::key::{Key, KeyMut};
+#[cfg(feature = "parse")]
+pub use crate::key::{is_valid_bare_key, parse_key, InvalidKey};
+pub use crate::key::{Key, KeyMut, KeyQuoteStyle};
 pub use crate::raw_string::RawString;
 pub use crate::repr::{Decor, Formatted, Repr};
 pub use crate::table::{
-    Entry, IntoIter, Iter, IterMut, OccupiedEntry, Table, TableLike, VacantEntry,
+    Entry, IntoIter, Iter, IterMut, MergeStrategy, OccupiedEntry, Table, TableLike, VacantEntry,
 };
-pub use crate::value::Value;
+pub use crate::value::{CoerceError, DatetimeFormat, StringQuoteStyle, Value, ValueKind};
+#[cfg(feature = "display")]
+pub use crate::writer::{DocumentWriter, WriteError};
 pub use toml_datetime::*;
 
+// The parts of the `std` prelude that come from `alloc`, for `no_std` builds
+pub(crate) mod prelude {
+    pub(crate) use alloc::borrow::ToOwned;
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::format;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec::Vec;
+}
+
 // Prevent users from some traits.
 pub(crate) mod private {
     pub trait Sealed {}
     impl Sealed for usize {}
     impl Sealed for str {}
-    impl Sealed for String {}
+    impl Sealed for alloc::string::String {}
     impl Sealed for i64 {}
     impl Sealed for f64 {}
     impl Sealed for bool {}
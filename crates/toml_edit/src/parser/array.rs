@@ -13,11 +13,14 @@ use crate::parser::prelude::*;
 // ;; Array
 
 // array = array-open array-values array-close
-pub(crate) fn array<'i>(check: RecursionCheck) -> impl Parser<Input<'i>, Array, ContextError> {
+pub(crate) fn array<'i>(
+    check: RecursionCheck,
+    ext: Extensions,
+) -> impl Parser<Input<'i>, Array, ContextError> {
     trace("array", move |input: &mut Input<'i>| {
         delimited(
             ARRAY_OPEN,
-            cut_err(array_values(check)),
+            cut_err(array_values(check, ext)),
             cut_err(ARRAY_CLOSE)
                 .context(StrContext::Label("array"))
                 .context(StrContext::Expected(StrContextValue::CharLiteral(']'))),
@@ -40,23 +43,24 @@ const ARRAY_SEP: u8 = b',';
 //                  array-value / ws-comment-newline ]
 pub(crate) fn array_values<'i>(
     check: RecursionCheck,
+    ext: Extensions,
 ) -> impl Parser<Input<'i>, Array, ContextError> {
     move |input: &mut Input<'i>| {
         let check = check.recursing(input)?;
         (
-            opt(
-                (separated1(array_value(check), ARRAY_SEP), opt(ARRAY_SEP)).map(
-                    |(v, trailing): (Vec<Value>, Option<u8>)| {
-                        (
-                            Array::with_vec(v.into_iter().map(Item::Value).collect()),
-                            trailing.is_some(),
-                        )
-                    },
-                ),
-            ),
+            opt((
+                separated1(array_value(check, ext), ARRAY_SEP),
+                opt(ARRAY_SEP),
+            )
+                .map(|(v, trailing): (Vec<Value>, Option<u8>)| {
+                    (
+                        Array::with_vec(v.into_iter().map(Item::Value).collect()),
+                        trailing.is_some(),
+                    )
+                })),
             ws_comment_newline.span(),
         )
-            .try_map::<_, _, std::str::Utf8Error>(|(array, trailing)| {
+            .try_map::<_, _, core::str::Utf8Error>(|(array, trailing)| {
                 let (mut array, comma) = array.unwrap_or_default();
                 array.set_trailing_comma(comma);
                 array.set_trailing(RawString::with_span(trailing));
@@ -68,11 +72,12 @@ pub(crate) fn array_values<'i>(
 
 pub(crate) fn array_value<'i>(
     check: RecursionCheck,
+    ext: Extensions,
 ) -> impl Parser<Input<'i>, Value, ContextError> {
     move |input: &mut Input<'i>| {
         (
             ws_comment_newline.span(),
-            value(check),
+            value(check, ext),
             ws_comment_newline.span(),
         )
             .map(|(ws1, v, ws2)| v.decorated(RawString::with_span(ws1), RawString::with_span(ws2)))
@@ -125,7 +130,7 @@ mod test {
         ];
         for input in inputs {
             dbg!(input);
-            let mut parsed = array(Default::default()).parse(new_input(input));
+            let mut parsed = array(Default::default(), Default::default()).parse(new_input(input));
             if let Ok(parsed) = &mut parsed {
                 parsed.despan(input);
             }
@@ -138,7 +143,7 @@ mod test {
         let invalid_inputs = [r#"["#, r#"[,]"#, r#"[,2]"#, r#"[1e165,,]"#];
         for input in invalid_inputs {
             dbg!(input);
-            let mut parsed = array(Default::default()).parse(new_input(input));
+            let mut parsed = array(Default::default(), Default::default()).parse(new_input(input));
             if let Ok(parsed) = &mut parsed {
                 parsed.despan(input);
             }
@@ -1,4 +1,4 @@
-use std::cell::RefCell;
+use core::cell::RefCell;
 
 use winnow::combinator::cut_err;
 use winnow::combinator::eof;
@@ -30,31 +30,37 @@ use crate::RawString;
 //                ( ws keyval ws [ comment ] ) /
 //                ( ws table ws [ comment ] ) /
 //                  ws )
-pub(crate) fn document(input: &mut Input<'_>) -> PResult<Document> {
-    let state = RefCell::new(ParseState::default());
-    let state_ref = &state;
+pub(crate) fn document<'i>(ext: Extensions) -> impl Parser<Input<'i>, Document, ContextError> {
+    move |input: &mut Input<'i>| {
+        let state = RefCell::new(ParseState::default());
+        let state_ref = &state;
 
-    let _o = (
-        // Remove BOM if present
-        opt(b"\xEF\xBB\xBF"),
-        parse_ws(state_ref),
-        repeat(0.., (
-            dispatch! {peek(any);
-                crate::parser::trivia::COMMENT_START_SYMBOL => cut_err(parse_comment(state_ref)),
-                crate::parser::table::STD_TABLE_OPEN => cut_err(table(state_ref)),
-                crate::parser::trivia::LF |
-                crate::parser::trivia::CR => parse_newline(state_ref),
-                _ => cut_err(keyval(state_ref)),
-            },
+        let _o = (
+            // Remove BOM if present
+            opt(b"\xEF\xBB\xBF"),
             parse_ws(state_ref),
-        ))
-        .map(|()| ()),
-        eof,
-    )
-        .parse_next(input)?;
-    state.into_inner().into_document().map_err(|err| {
-        winnow::error::ErrMode::from_external_error(input, winnow::error::ErrorKind::Verify, err)
-    })
+            repeat(0.., (
+                dispatch! {peek(any);
+                    crate::parser::trivia::COMMENT_START_SYMBOL => cut_err(parse_comment(state_ref)),
+                    crate::parser::table::STD_TABLE_OPEN => cut_err(table(state_ref)),
+                    crate::parser::trivia::LF |
+                    crate::parser::trivia::CR => parse_newline(state_ref),
+                    _ => cut_err(keyval(state_ref, ext)),
+                },
+                parse_ws(state_ref),
+            ))
+            .map(|()| ()),
+            eof,
+        )
+            .parse_next(input)?;
+        state.into_inner().into_document().map_err(|err| {
+            winnow::error::ErrMode::from_external_error(
+                input,
+                winnow::error::ErrorKind::Verify,
+                err,
+            )
+        })
+    }
 }
 
 pub(crate) fn parse_comment<'s, 'i>(
@@ -93,49 +99,54 @@ pub(crate) fn parse_newline<'s, 'i>(
 
 pub(crate) fn keyval<'s, 'i>(
     state: &'s RefCell<ParseState>,
+    ext: Extensions,
 ) -> impl Parser<Input<'i>, (), ContextError> + 's {
     move |i: &mut Input<'i>| {
-        parse_keyval
+        parse_keyval(ext)
             .try_map(|(p, kv)| state.borrow_mut().on_keyval(p, kv))
             .parse_next(i)
     }
 }
 
 // keyval = key keyval-sep val
-pub(crate) fn parse_keyval(input: &mut Input<'_>) -> PResult<(Vec<Key>, TableKeyValue)> {
-    trace(
-        "keyval",
-        (
-            key,
-            cut_err((
-                one_of(KEYVAL_SEP)
-                    .context(StrContext::Expected(StrContextValue::CharLiteral('.')))
-                    .context(StrContext::Expected(StrContextValue::CharLiteral('='))),
-                (
-                    ws.span(),
-                    value(RecursionCheck::default()),
-                    line_trailing
-                        .context(StrContext::Expected(StrContextValue::CharLiteral('\n')))
-                        .context(StrContext::Expected(StrContextValue::CharLiteral('#'))),
-                ),
-            )),
-        )
-            .try_map::<_, _, std::str::Utf8Error>(|(key, (_, v))| {
-                let mut path = key;
-                let key = path.pop().expect("grammar ensures at least 1");
+pub(crate) fn parse_keyval<'i>(
+    ext: Extensions,
+) -> impl Parser<Input<'i>, (Vec<Key>, TableKeyValue), ContextError> {
+    move |input: &mut Input<'i>| {
+        trace(
+            "keyval",
+            (
+                key,
+                cut_err((
+                    one_of(KEYVAL_SEP)
+                        .context(StrContext::Expected(StrContextValue::CharLiteral('.')))
+                        .context(StrContext::Expected(StrContextValue::CharLiteral('='))),
+                    (
+                        ws.span(),
+                        value(RecursionCheck::default(), ext),
+                        line_trailing
+                            .context(StrContext::Expected(StrContextValue::CharLiteral('\n')))
+                            .context(StrContext::Expected(StrContextValue::CharLiteral('#'))),
+                    ),
+                )),
+            )
+                .try_map::<_, _, core::str::Utf8Error>(|(key, (_, v))| {
+                    let mut path = key;
+                    let key = path.pop().expect("grammar ensures at least 1");
 
-                let (pre, v, suf) = v;
-                let pre = RawString::with_span(pre);
-                let suf = RawString::with_span(suf);
-                let v = v.decorated(pre, suf);
-                Ok((
-                    path,
-                    TableKeyValue {
-                        key,
-                        value: Item::Value(v),
-                    },
-                ))
-            }),
-    )
-    .parse_next(input)
+                    let (pre, v, suf) = v;
+                    let pre = RawString::with_span(pre);
+                    let suf = RawString::with_span(suf);
+                    let v = v.decorated(pre, suf);
+                    Ok((
+                        path,
+                        TableKeyValue {
+                            key,
+                            value: Item::Value(v),
+                        },
+                    ))
+                }),
+        )
+        .parse_next(input)
+    }
 }
@@ -1,6 +1,6 @@
-use std::error::Error as StdError;
-use std::fmt::{Display, Formatter, Result};
+use core::fmt::{Display, Formatter, Result};
 
+use crate::prelude::*;
 use crate::Key;
 
 #[derive(Debug, Clone)]
@@ -51,7 +51,8 @@ impl CustomError {
     }
 }
 
-impl StdError for CustomError {
+#[cfg(feature = "std")]
+impl std::error::Error for CustomError {
     fn description(&self) -> &'static str {
         "TOML parse error"
     }
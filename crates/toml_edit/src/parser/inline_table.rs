@@ -20,11 +20,12 @@ use indexmap::map::Entry;
 // inline-table = inline-table-open inline-table-keyvals inline-table-close
 pub(crate) fn inline_table<'i>(
     check: RecursionCheck,
+    ext: Extensions,
 ) -> impl Parser<Input<'i>, InlineTable, ContextError> {
     trace("inline-table", move |input: &mut Input<'i>| {
         delimited(
             INLINE_TABLE_OPEN,
-            cut_err(inline_table_keyvals(check).try_map(|(kv, p)| table_from_pairs(kv, p))),
+            cut_err(inline_table_keyvals(check, ext).try_map(|(kv, p)| table_from_pairs(kv, p))),
             cut_err(INLINE_TABLE_CLOSE)
                 .context(StrContext::Label("inline table"))
                 .context(StrContext::Expected(StrContextValue::CharLiteral('}'))),
@@ -120,11 +121,12 @@ pub(crate) const KEYVAL_SEP: u8 = b'=';
 
 fn inline_table_keyvals<'i>(
     check: RecursionCheck,
+    ext: Extensions,
 ) -> impl Parser<Input<'i>, (Vec<(Vec<Key>, TableKeyValue)>, RawString), ContextError> {
     move |input: &mut Input<'i>| {
         let check = check.recursing(input)?;
         (
-            separated0(keyval(check), INLINE_TABLE_SEP),
+            separated0(keyval(check, ext), INLINE_TABLE_SEP),
             ws.span().map(RawString::with_span),
         )
             .parse_next(input)
@@ -133,6 +135,7 @@ fn inline_table_keyvals<'i>(
 
 fn keyval<'i>(
     check: RecursionCheck,
+    ext: Extensions,
 ) -> impl Parser<Input<'i>, (Vec<Key>, TableKeyValue), ContextError> {
     move |input: &mut Input<'i>| {
         (
@@ -141,7 +144,7 @@ fn keyval<'i>(
                 one_of(KEYVAL_SEP)
                     .context(StrContext::Expected(StrContextValue::CharLiteral('.')))
                     .context(StrContext::Expected(StrContextValue::CharLiteral('='))),
-                (ws.span(), value(check), ws.span()),
+                (ws.span(), value(check, ext), ws.span()),
             )),
         )
             .map(|(key, (_, v))| {
@@ -181,7 +184,8 @@ mod test {
         ];
         for input in inputs {
             dbg!(input);
-            let mut parsed = inline_table(Default::default()).parse(new_input(input));
+            let mut parsed =
+                inline_table(Default::default(), Default::default()).parse(new_input(input));
             if let Ok(parsed) = &mut parsed {
                 parsed.despan(input);
             }
@@ -194,7 +198,8 @@ mod test {
         let invalid_inputs = [r#"{a = 1e165"#, r#"{ hello = "world", a = 2, hello = 1}"#];
         for input in invalid_inputs {
             dbg!(input);
-            let mut parsed = inline_table(Default::default()).parse(new_input(input));
+            let mut parsed =
+                inline_table(Default::default(), Default::default()).parse(new_input(input));
             if let Ok(parsed) = &mut parsed {
                 parsed.despan(input);
             }
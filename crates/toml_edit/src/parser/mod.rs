@@ -15,18 +15,167 @@ pub(crate) mod value;
 
 pub use crate::error::TomlError;
 
+use crate::prelude::*;
+
 pub(crate) fn parse_document(raw: &str) -> Result<crate::Document, TomlError> {
+    parse_document_with(raw, prelude::Extensions::default())
+}
+
+pub(crate) fn parse_document_with(
+    raw: &str,
+    ext: prelude::Extensions,
+) -> Result<crate::Document, TomlError> {
     use prelude::*;
 
     let b = new_input(raw);
-    let mut doc = document::document
+    let mut doc = document::document(ext)
         .parse(b)
         .map_err(|e| TomlError::new(e, b))?;
     doc.span = Some(0..(raw.len()));
     doc.original = Some(raw.to_owned());
+    doc.line_ending = crate::document::LineEnding::detect(raw);
+    doc.bom = raw.starts_with('\u{FEFF}');
     Ok(doc)
 }
 
+/// Parse a document, skipping over the lines that fail to parse
+///
+/// Each time parsing fails, the offending lines are blanked out and parsing is retried, so
+/// offsets are kept and errors are reported against `raw`.  An unterminated string is skipped up
+/// to the end of its line, or for a multi-line string, up to the next table header.
+pub(crate) fn parse_document_recover(raw: &str) -> (crate::Document, Vec<TomlError>) {
+    let mut normalized = raw.to_owned();
+    let mut errors = Vec::new();
+    loop {
+        let err = match parse_document(&normalized) {
+            Ok(doc) => return (doc, errors),
+            Err(err) => err,
+        };
+        let bytes = normalized.as_bytes();
+        let offset = err
+            .span()
+            .map(|span| span.start)
+            .unwrap_or_default()
+            .min(bytes.len());
+        let (skipped, span) = match unterminated_string(bytes) {
+            // A multi-line string runs to the end of the input
+            Some((quotes, end))
+                if quotes.start <= offset && (offset <= end || quotes.len() == 3) =>
+            {
+                // The error of a multi-line string is at its end, far from the culprit
+                let span = if quotes.len() == 3 {
+                    Some(quotes.clone())
+                } else {
+                    err.span()
+                };
+                (line_start(bytes, quotes.start)..end, span)
+            }
+            _ => {
+                let is_blank = |range: core::ops::Range<usize>| {
+                    bytes[range]
+                        .iter()
+                        .all(|b| matches!(b, b' ' | b'\t' | b'\r' | b'\n'))
+                };
+                let mut end = bytes[offset..]
+                    .iter()
+                    .position(|b| *b == b'\n')
+                    .map_or(bytes.len(), |i| offset + i);
+                let mut start = line_start(bytes, offset);
+                // The error may be reported past a blank line, e.g. at the end of the input
+                while start > 0 && is_blank(start..end) {
+                    start = line_start(bytes, start - 1);
+                }
+                // Always skip something, so the next attempt makes progress
+                if is_blank(start..end) {
+                    end = bytes.len();
+                }
+                (start..end, err.span())
+            }
+        };
+        // Keep the line endings, so `\r\n` stays a newline
+        let blank = skipped
+            .clone()
+            .map(|i| match bytes[i] {
+                b'\n' => '\n',
+                b'\r' if bytes.get(i + 1) == Some(&b'\n') => '\r',
+                _ => ' ',
+            })
+            .collect::<String>();
+        normalized.replace_range(skipped, &blank);
+        errors.push(err.with_original(raw, span));
+    }
+}
+
+/// The offset of the first byte of the line containing `offset`
+fn line_start(input: &[u8], offset: usize) -> usize {
+    input[..offset]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |i| i + 1)
+}
+
+/// The opening quotes of the first string missing its closing quotes, and where parsing can
+/// resume after it
+///
+/// A single-line string is skipped up to its line's newline, and a multi-line string up to the
+/// next line starting with `[`, or to the end of the input.
+fn unterminated_string(input: &[u8]) -> Option<(core::ops::Range<usize>, usize)> {
+    let mut i = 0;
+    while i < input.len() {
+        match input[i] {
+            b'#' => {
+                i += input[i..].iter().position(|b| *b == b'\n')?;
+            }
+            quote @ (b'"' | b'\'') => {
+                let start = i;
+                let escapes = quote == b'"';
+                if input[i..].starts_with(&[quote; 3]) {
+                    i += 3;
+                    loop {
+                        match input.get(i) {
+                            None => {
+                                let mut end = start;
+                                while let Some(newline) =
+                                    input[end..].iter().position(|b| *b == b'\n')
+                                {
+                                    end += newline + 1;
+                                    if input.get(end) == Some(&b'[') {
+                                        return Some((start..start + 3, end));
+                                    }
+                                }
+                                return Some((start..start + 3, input.len()));
+                            }
+                            Some(b'\\') if escapes => i += 2,
+                            Some(_) if input[i..].starts_with(&[quote; 3]) => {
+                                i += 3;
+                                // Up to two quotes may close the content
+                                while input.get(i) == Some(&quote) {
+                                    i += 1;
+                                }
+                                break;
+                            }
+                            Some(_) => i += 1,
+                        }
+                    }
+                    continue;
+                }
+                i += 1;
+                loop {
+                    match input.get(i) {
+                        None | Some(b'\n') => return Some((start..start + 1, i)),
+                        Some(b'\\') if escapes && input.get(i + 1) != Some(&b'\n') => i += 2,
+                        Some(b) if *b == quote => break,
+                        Some(_) => i += 1,
+                    }
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
 pub(crate) fn parse_key(raw: &str) -> Result<crate::Key, TomlError> {
     use prelude::*;
 
@@ -73,6 +222,7 @@ pub(crate) fn parse_value(raw: &str) -> Result<crate::Value, TomlError> {
 }
 
 pub(crate) mod prelude {
+    pub(crate) use crate::prelude::*;
     pub(crate) use winnow::combinator::dispatch;
     pub(crate) use winnow::error::ContextError;
     pub(crate) use winnow::error::FromExternalError;
@@ -87,6 +237,15 @@ pub(crate) mod prelude {
         winnow::Located::new(winnow::BStr::new(s))
     }
 
+    /// Deviations from the TOML grammar that the caller opted into
+    #[derive(Copy, Clone, Debug, Default)]
+    pub(crate) struct Extensions {
+        /// Accept zeros before the digits of a decimal number, like in `007` or `-01.5`
+        pub(crate) leading_zeros: bool,
+        /// Accept integers up to `u64::MAX`, storing those above `i64::MAX` with the same bits
+        pub(crate) u64_integers: bool,
+    }
+
     #[cfg(not(feature = "unbounded"))]
     #[derive(Copy, Clone, Debug, Default)]
     pub(crate) struct RecursionCheck {
@@ -264,4 +423,18 @@ $"#];
             assert!(parsed.is_err(), "Input: {:?}", input);
         }
     }
+
+    #[test]
+    fn unterminated_strings() {
+        let cases = [
+            ("a = \"abc\nb = 1\n", Some((4..5, 8))),
+            ("a = 'x\"y' # it's\nc = 'bad\n", Some((21..22, 25))),
+            ("a = \"\"\"\nabc\n\n[t]\nb = 1\n", Some((4..7, 13))),
+            ("a = \"\"\"x\\\"\"\"\"\"\n", None),
+            ("a = '''x''''\nb = \"\"\n", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(unterminated_string(input.as_bytes()), expected, "{input:?}");
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::ops::RangeInclusive;
+use core::ops::RangeInclusive;
 
 use winnow::combinator::alt;
 use winnow::combinator::cut_err;
@@ -7,6 +7,7 @@ use winnow::combinator::peek;
 use winnow::combinator::preceded;
 use winnow::combinator::repeat;
 use winnow::combinator::rest;
+use winnow::combinator::terminated;
 use winnow::token::one_of;
 use winnow::token::tag;
 use winnow::token::take;
@@ -38,57 +39,87 @@ const FALSE: &[u8] = b"false";
 // ;; Integer
 
 // integer = dec-int / hex-int / oct-int / bin-int
-pub(crate) fn integer(input: &mut Input<'_>) -> PResult<i64> {
-    trace("integer",
-    dispatch! {peek(opt::<_, &[u8], _, _>(take(2usize)));
-        Some(b"0x") => cut_err(hex_int.try_map(|s| i64::from_str_radix(&s.replace('_', ""), 16))),
-        Some(b"0o") => cut_err(oct_int.try_map(|s| i64::from_str_radix(&s.replace('_', ""), 8))),
-        Some(b"0b") => cut_err(bin_int.try_map(|s| i64::from_str_radix(&s.replace('_', ""), 2))),
-        _ => dec_int.and_then(cut_err(rest
-            .try_map(|s: &str| s.replace('_', "").parse())))
+pub(crate) fn integer<'i>(ext: Extensions) -> impl Parser<Input<'i>, i64, ContextError> {
+    move |input: &mut Input<'i>| {
+        trace("integer",
+        dispatch! {peek(opt::<_, &[u8], _, _>(take(2usize)));
+            Some(b"0x") => cut_err(hex_int.try_map(|s| from_str_radix(&s.replace('_', ""), 16, ext))),
+            Some(b"0o") => cut_err(oct_int.try_map(|s| from_str_radix(&s.replace('_', ""), 8, ext))),
+            Some(b"0b") => cut_err(bin_int.try_map(|s| from_str_radix(&s.replace('_', ""), 2, ext))),
+            _ => dec_int(ext).and_then(cut_err(rest
+                .try_map(|s: &str| from_str_radix(&s.replace('_', ""), 10, ext))))
+        })
+        .parse_next(input)
+    }
+}
+
+// Not part of the TOML grammar: `u64` integers, when `Extensions::u64_integers` is set
+fn from_str_radix(
+    digits: &str,
+    radix: u32,
+    ext: Extensions,
+) -> Result<i64, core::num::ParseIntError> {
+    i64::from_str_radix(digits, radix).or_else(|err| match u64::from_str_radix(digits, radix) {
+        Ok(u) if ext.u64_integers => Ok(u as i64),
+        _ => Err(err),
     })
-    .parse_next(input)
 }
 
 // dec-int = [ minus / plus ] unsigned-dec-int
 // unsigned-dec-int = DIGIT / digit1-9 1*( DIGIT / underscore DIGIT )
-pub(crate) fn dec_int<'i>(input: &mut Input<'i>) -> PResult<&'i str> {
-    trace(
-        "dec-int",
-        (
-            opt(one_of((b'+', b'-'))),
-            alt((
-                (
-                    one_of(DIGIT1_9),
-                    repeat(
-                        0..,
-                        alt((
-                            digit.value(()),
-                            (
-                                one_of(b'_'),
-                                cut_err(digit).context(StrContext::Expected(
-                                    StrContextValue::Description("digit"),
-                                )),
-                            )
-                                .value(()),
-                        )),
+pub(crate) fn dec_int<'i>(ext: Extensions) -> impl Parser<Input<'i>, &'i str, ContextError> {
+    move |input: &mut Input<'i>| {
+        trace(
+            "dec-int",
+            (
+                opt(one_of((b'+', b'-'))),
+                leading_zeros(ext),
+                alt((
+                    (
+                        one_of(DIGIT1_9),
+                        repeat(
+                            0..,
+                            alt((
+                                digit.value(()),
+                                (
+                                    one_of(b'_'),
+                                    cut_err(digit).context(StrContext::Expected(
+                                        StrContextValue::Description("digit"),
+                                    )),
+                                )
+                                    .value(()),
+                            )),
+                        )
+                        .map(|()| ()),
                     )
-                    .map(|()| ()),
-                )
-                    .value(()),
-                digit.value(()),
-            )),
+                        .value(()),
+                    digit.value(()),
+                )),
+            )
+                .recognize()
+                .map(|b: &[u8]| unsafe {
+                    from_utf8_unchecked(b, "`digit` and `_` filter out non-ASCII")
+                })
+                .context(StrContext::Label("integer")),
         )
-            .recognize()
-            .map(|b: &[u8]| unsafe {
-                from_utf8_unchecked(b, "`digit` and `_` filter out non-ASCII")
-            })
-            .context(StrContext::Label("integer")),
-    )
-    .parse_next(input)
+        .parse_next(input)
+    }
 }
 const DIGIT1_9: RangeInclusive<u8> = b'1'..=b'9';
 
+// Not part of the TOML grammar: the zeros of `007`, when `Extensions::leading_zeros` is set
+fn leading_zeros<'i>(ext: Extensions) -> impl Parser<Input<'i>, (), ContextError> {
+    move |input: &mut Input<'i>| {
+        if ext.leading_zeros {
+            repeat(0.., terminated(b'0', peek(digit)))
+                .map(|()| ())
+                .parse_next(input)
+        } else {
+            Ok(())
+        }
+    }
+}
+
 // hex-prefix = %x30.78               ; 0x
 // hex-int = hex-prefix HEXDIG *( HEXDIG / underscore HEXDIG )
 pub(crate) fn hex_int<'i>(input: &mut Input<'i>) -> PResult<&'i str> {
@@ -195,34 +226,38 @@ const DIGIT0_1: RangeInclusive<u8> = b'0'..=b'1';
 // float = float-int-part ( exp / frac [ exp ] )
 // float =/ special-float
 // float-int-part = dec-int
-pub(crate) fn float(input: &mut Input<'_>) -> PResult<f64> {
-    trace(
-        "float",
-        alt((
-            float_.and_then(cut_err(
-                rest.try_map(|s: &str| s.replace('_', "").parse())
-                    .verify(|f: &f64| *f != f64::INFINITY),
-            )),
-            special_float,
-        ))
-        .context(StrContext::Label("floating-point number")),
-    )
-    .parse_next(input)
+pub(crate) fn float<'i>(ext: Extensions) -> impl Parser<Input<'i>, f64, ContextError> {
+    move |input: &mut Input<'i>| {
+        trace(
+            "float",
+            alt((
+                float_(ext).and_then(cut_err(
+                    rest.try_map(|s: &str| s.replace('_', "").parse())
+                        .verify(|f: &f64| *f != f64::INFINITY),
+                )),
+                special_float,
+            ))
+            .context(StrContext::Label("floating-point number")),
+        )
+        .parse_next(input)
+    }
 }
 
-pub(crate) fn float_<'i>(input: &mut Input<'i>) -> PResult<&'i str> {
-    (
-        dec_int,
-        alt((exp.void(), (frac.void(), opt(exp.void())).void())),
-    )
-        .recognize()
-        .map(|b: &[u8]| unsafe {
-            from_utf8_unchecked(
-                b,
-                "`dec_int`, `one_of`, `exp`, and `frac` filter out non-ASCII",
-            )
-        })
-        .parse_next(input)
+pub(crate) fn float_<'i>(ext: Extensions) -> impl Parser<Input<'i>, &'i str, ContextError> {
+    move |input: &mut Input<'i>| {
+        (
+            dec_int(ext),
+            alt((exp.void(), (frac.void(), opt(exp.void())).void())),
+        )
+            .recognize()
+            .map(|b: &[u8]| unsafe {
+                from_utf8_unchecked(
+                    b,
+                    "`dec_int`, `one_of`, `exp`, and `frac` filter out non-ASCII",
+                )
+            })
+            .parse_next(input)
+    }
 }
 
 // frac = decimal-point zero-prefixable-int
@@ -301,7 +336,10 @@ pub(crate) fn inf(input: &mut Input<'_>) -> PResult<f64> {
 const INF: &[u8] = b"inf";
 // nan = %x6e.61.6e  ; nan
 pub(crate) fn nan(input: &mut Input<'_>) -> PResult<f64> {
-    tag(NAN).value(f64::NAN.copysign(1.0)).parse_next(input)
+    // A positive quiet NaN, as `f64::copysign` needs `std`
+    tag(NAN)
+        .value(f64::from_bits(0x7ff8_0000_0000_0000))
+        .parse_next(input)
 }
 const NAN: &[u8] = b"nan";
 
@@ -342,13 +380,53 @@ mod test {
         ];
         for &(input, expected) in &cases {
             dbg!(input);
-            let parsed = integer.parse(new_input(input));
+            let parsed = integer(Extensions::default()).parse(new_input(input));
             assert_eq!(parsed, Ok(expected), "Parsing {input:?}");
         }
 
         let overflow = "1000000000000000000000000000000000";
-        let parsed = integer.parse(new_input(overflow));
+        let parsed = integer(Extensions::default()).parse(new_input(overflow));
         assert!(parsed.is_err());
+
+        let ext = Extensions {
+            u64_integers: true,
+            ..Default::default()
+        };
+        let cases = [
+            ("18446744073709551615", -1),
+            ("0xffff_ffff_ffff_fffe", -2),
+            ("9223372036854775808", i64::MIN),
+        ];
+        for (input, expected) in cases {
+            let parsed = integer(Extensions::default()).parse(new_input(input));
+            assert!(parsed.is_err(), "Parsing {input:?}");
+            let parsed = integer(ext).parse(new_input(input));
+            assert_eq!(parsed, Ok(expected), "Parsing {input:?}");
+        }
+        assert!(integer(ext)
+            .parse(new_input("18446744073709551616"))
+            .is_err());
+        assert!(integer(ext)
+            .parse(new_input("-9223372036854775809"))
+            .is_err());
+    }
+
+    #[test]
+    fn leading_zeros() {
+        let ext = Extensions {
+            leading_zeros: true,
+            ..Default::default()
+        };
+        for (input, expected) in [("007", 7), ("-01", -1), ("+000", 0)] {
+            let parsed = integer(Extensions::default()).parse(new_input(input));
+            assert!(parsed.is_err(), "Parsing {input:?}");
+            let parsed = integer(ext).parse(new_input(input));
+            assert_eq!(parsed, Ok(expected), "Parsing {input:?}");
+        }
+
+        let parsed = float(ext).parse(new_input("-01.5")).unwrap();
+        assert_float_eq(parsed, -1.5);
+        assert!(integer(ext).parse(new_input("0_1")).is_err());
     }
 
     #[track_caller]
@@ -389,11 +467,13 @@ mod test {
         ];
         for &(input, expected) in &cases {
             dbg!(input);
-            let parsed = float.parse(new_input(input)).unwrap();
+            let parsed = float(Extensions::default())
+                .parse(new_input(input))
+                .unwrap();
             assert_float_eq(parsed, expected);
 
             let overflow = "9e99999";
-            let parsed = float.parse(new_input(overflow));
+            let parsed = float(Extensions::default()).parse(new_input(overflow));
             assert!(parsed.is_err(), "{:?}", parsed);
         }
     }
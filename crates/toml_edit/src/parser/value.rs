@@ -15,7 +15,10 @@ use crate::RawString;
 use crate::Value;
 
 // val = string / boolean / array / inline-table / date-time / float / integer
-pub(crate) fn value<'i>(check: RecursionCheck) -> impl Parser<Input<'i>, v::Value, ContextError> {
+pub(crate) fn value<'i>(
+    check: RecursionCheck,
+    ext: Extensions,
+) -> impl Parser<Input<'i>, v::Value, ContextError> {
     move |input: &mut Input<'i>| {
         dispatch!{peek(any);
             crate::parser::strings::QUOTATION_MARK |
@@ -24,29 +27,29 @@ pub(crate) fn value<'i>(check: RecursionCheck) -> impl Parser<Input<'i>, v::Valu
                     s.into_owned()
                 ))
             }),
-            crate::parser::array::ARRAY_OPEN => array(check).map(v::Value::Array),
-            crate::parser::inline_table::INLINE_TABLE_OPEN => inline_table(check).map(v::Value::InlineTable),
+            crate::parser::array::ARRAY_OPEN => array(check, ext).map(v::Value::Array),
+            crate::parser::inline_table::INLINE_TABLE_OPEN => inline_table(check, ext).map(v::Value::InlineTable),
             // Date/number starts
             b'+' | b'-' | b'0'..=b'9' => {
                 // Uncommon enough not to be worth optimizing at this time
                 alt((
                     date_time
                         .map(v::Value::from),
-                    float
+                    float(ext)
                         .map(v::Value::from),
-                    integer
+                    integer(ext)
                         .map(v::Value::from),
                 ))
             },
             // Report as if they were numbers because its most likely a typo
             b'_' => {
-                    integer
+                    integer(ext)
                         .map(v::Value::from)
                 .context(StrContext::Expected(StrContextValue::Description("leading digit")))
             },
             // Report as if they were numbers because its most likely a typo
             b'.' =>  {
-                    float
+                    float(ext)
                         .map(v::Value::from)
                 .context(StrContext::Expected(StrContextValue::Description("leading digit")))
             },
@@ -87,7 +90,7 @@ pub(crate) fn value<'i>(check: RecursionCheck) -> impl Parser<Input<'i>, v::Valu
     }
 }
 
-fn apply_raw(mut val: Value, span: std::ops::Range<usize>) -> Result<Value, std::str::Utf8Error> {
+fn apply_raw(mut val: Value, span: core::ops::Range<usize>) -> Result<Value, core::str::Utf8Error> {
     match val {
         Value::String(ref mut f) => {
             let raw = RawString::with_span(span);
@@ -147,7 +150,7 @@ trimmed in raw strings.
         ];
         for input in inputs {
             dbg!(input);
-            let mut parsed = value(Default::default()).parse(new_input(input));
+            let mut parsed = value(Default::default(), Default::default()).parse(new_input(input));
             if let Ok(parsed) = &mut parsed {
                 parsed.despan(input);
             }
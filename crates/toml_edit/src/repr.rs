@@ -1,5 +1,6 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
 
+use crate::prelude::*;
 use crate::RawString;
 
 /// A value together with its `to_string` representation,
@@ -61,7 +62,7 @@ where
     }
 
     /// Returns the location within the original document
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         self.repr.as_ref().and_then(|r| r.span())
     }
 
@@ -88,12 +89,12 @@ where
     }
 }
 
-impl<T> std::fmt::Debug for Formatted<T>
+impl<T> core::fmt::Debug for Formatted<T>
 where
-    T: std::fmt::Debug,
+    T: core::fmt::Debug,
 {
     #[inline]
-    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         let mut d = formatter.debug_struct("Formatted");
         d.field("value", &self.value);
         match &self.repr {
@@ -106,11 +107,11 @@ where
 }
 
 #[cfg(feature = "display")]
-impl<T> std::fmt::Display for Formatted<T>
+impl<T> core::fmt::Display for Formatted<T>
 where
     T: ValueRepr,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, None, ("", ""))
     }
 }
@@ -125,7 +126,7 @@ pub trait ValueRepr: crate::private::Sealed {
 mod inner {
     use super::ValueRepr;
 
-    impl ValueRepr for String {}
+    impl ValueRepr for alloc::string::String {}
     impl ValueRepr for i64 {}
     impl ValueRepr for f64 {}
     impl ValueRepr for bool {}
@@ -151,7 +152,7 @@ impl Repr {
     }
 
     /// Returns the location within the original document
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         self.raw_value.span()
     }
 
@@ -160,14 +161,14 @@ impl Repr {
     }
 
     #[cfg(feature = "display")]
-    pub(crate) fn encode(&self, buf: &mut dyn std::fmt::Write, input: &str) -> std::fmt::Result {
+    pub(crate) fn encode(&self, buf: &mut dyn core::fmt::Write, input: &str) -> core::fmt::Result {
         self.as_raw().encode(buf, input)
     }
 }
 
-impl std::fmt::Debug for Repr {
+impl core::fmt::Debug for Repr {
     #[inline]
-    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         self.raw_value.fmt(formatter)
     }
 }
@@ -190,6 +191,37 @@ impl Decor {
         }
     }
 
+    /// Creates a comment prefix, for use with [`Decor::set_prefix`]
+    ///
+    /// Each line of `text` becomes its own comment.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut decor = toml_edit::Decor::default();
+    /// decor.set_prefix(toml_edit::Decor::blank_lines(1) + &toml_edit::Decor::with_comment("hi"));
+    /// assert_eq!(decor.prefix().and_then(|p| p.as_str()), Some("\n# hi\n"));
+    /// ```
+    pub fn with_comment(text: &str) -> String {
+        let mut prefix = String::new();
+        for line in text.lines() {
+            prefix.push('#');
+            if !line.is_empty() {
+                prefix.push(' ');
+                prefix.push_str(line);
+            }
+            prefix.push('\n');
+        }
+        if prefix.is_empty() {
+            prefix.push_str("#\n");
+        }
+        prefix
+    }
+
+    /// Creates a prefix of `n` blank lines, for use with [`Decor::set_prefix`]
+    pub fn blank_lines(n: usize) -> String {
+        "\n".repeat(n)
+    }
+
     /// Go back to default decor
     pub fn clear(&mut self) {
         self.prefix = None;
@@ -204,10 +236,10 @@ impl Decor {
     #[cfg(feature = "display")]
     pub(crate) fn prefix_encode(
         &self,
-        buf: &mut dyn std::fmt::Write,
+        buf: &mut dyn core::fmt::Write,
         input: Option<&str>,
         default: &str,
-    ) -> std::fmt::Result {
+    ) -> core::fmt::Result {
         if let Some(prefix) = self.prefix() {
             prefix.encode_with_default(buf, input, default)
         } else {
@@ -228,10 +260,10 @@ impl Decor {
     #[cfg(feature = "display")]
     pub(crate) fn suffix_encode(
         &self,
-        buf: &mut dyn std::fmt::Write,
+        buf: &mut dyn core::fmt::Write,
         input: Option<&str>,
         default: &str,
-    ) -> std::fmt::Result {
+    ) -> core::fmt::Result {
         if let Some(suffix) = self.suffix() {
             suffix.encode_with_default(buf, input, default)
         } else {
@@ -254,9 +286,21 @@ impl Decor {
     }
 }
 
-impl std::fmt::Debug for Decor {
+/// Splits the leading blank lines off of a prefix, keeping comments and indentation in the rest
+pub(crate) fn split_blank_lines(prefix: &str) -> (&str, &str) {
+    let mut rest = prefix;
+    while let Some((line, remaining)) = rest.split_once('\n') {
+        if !line.trim().is_empty() {
+            break;
+        }
+        rest = remaining;
+    }
+    prefix.split_at(prefix.len() - rest.len())
+}
+
+impl core::fmt::Debug for Decor {
     #[inline]
-    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         let mut d = formatter.debug_struct("Decor");
         match &self.prefix {
             Some(r) => d.field("prefix", r),
@@ -1,11 +1,12 @@
-use std::iter::FromIterator;
+use core::iter::FromIterator;
 
 use indexmap::map::IndexMap;
 
 use crate::key::Key;
-use crate::repr::Decor;
+use crate::prelude::*;
+use crate::repr::{split_blank_lines, Decor};
 use crate::value::DEFAULT_VALUE_DECOR;
-use crate::{InlineTable, InternalString, Item, KeyMut, Value};
+use crate::{ArrayOfTables, InlineTable, InternalString, Item, KeyMut, RawString, Value};
 
 /// Type representing a TOML non-inline table
 #[derive(Clone, Debug, Default)]
@@ -20,7 +21,7 @@ pub struct Table {
     //
     // `None` for user created tables (can be overridden with `set_position`)
     doc_position: Option<usize>,
-    pub(crate) span: Option<std::ops::Range<usize>>,
+    pub(crate) span: Option<core::ops::Range<usize>>,
     pub(crate) items: KeyValuePairs,
 }
 
@@ -56,6 +57,34 @@ impl Table {
         t.fmt();
         t
     }
+
+    /// Convert a small table to an inline table
+    ///
+    /// Returns `None` unless the table holds at most `max_entries` scalar values, and nothing
+    /// else, and the inline table renders shorter than `max_len`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let doc = "[point]\nx = 1\ny = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// let point = doc["point"].as_table().unwrap();
+    /// assert_eq!(point.inline_if(2, 20).unwrap().to_string(), "{ x = 1, y = 2 }");
+    /// assert!(point.inline_if(1, 20).is_none());
+    /// assert!(point.inline_if(2, 10).is_none());
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn inline_if(&self, max_entries: usize, max_len: usize) -> Option<InlineTable> {
+        let is_scalar = |item: &Item| {
+            item.as_value()
+                .map_or(false, |v| !v.is_array() && !v.is_inline_table())
+        };
+        if self.len() > max_entries || !self.iter().all(|(_, item)| is_scalar(item)) {
+            return None;
+        }
+        let inline = self.clone().into_inline_table();
+        (inline.to_string().len() < max_len).then_some(inline)
+    }
 }
 
 /// Formatting
@@ -107,8 +136,10 @@ impl Table {
     ///
     /// Doesn't affect subtables or subarrays.
     pub fn sort_values(&mut self) {
+        let first = self.first_value_key();
         // Assuming standard tables have their doc_position set and this won't negatively impact them
         self.items.sort_keys();
+        self.rebalance_first_decor(first);
         for kv in self.items.values_mut() {
             match &mut kv.value {
                 Item::Table(table) if table.is_dotted() => {
@@ -125,24 +156,26 @@ impl Table {
     /// values or their combination as needed).
     pub fn sort_values_by<F>(&mut self, mut compare: F)
     where
-        F: FnMut(&Key, &Item, &Key, &Item) -> std::cmp::Ordering,
+        F: FnMut(&Key, &Item, &Key, &Item) -> core::cmp::Ordering,
     {
         self.sort_values_by_internal(&mut compare);
     }
 
     fn sort_values_by_internal<F>(&mut self, compare: &mut F)
     where
-        F: FnMut(&Key, &Item, &Key, &Item) -> std::cmp::Ordering,
+        F: FnMut(&Key, &Item, &Key, &Item) -> core::cmp::Ordering,
     {
         let modified_cmp = |_: &InternalString,
                             val1: &TableKeyValue,
                             _: &InternalString,
                             val2: &TableKeyValue|
-         -> std::cmp::Ordering {
+         -> core::cmp::Ordering {
             compare(&val1.key, &val1.value, &val2.key, &val2.value)
         };
 
+        let first = self.first_value_key();
         self.items.sort_by(modified_cmp);
+        self.rebalance_first_decor(first);
 
         for kv in self.items.values_mut() {
             match &mut kv.value {
@@ -154,6 +187,81 @@ impl Table {
         }
     }
 
+    /// Swaps the positions of two keys, returning `false` if either is missing.
+    ///
+    /// Each value keeps its key's decor, except that the blank lines before the first key/value
+    /// pair stay in place.  Subtables with a header are ordered by [`Table::position`] instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "a = 1\nb = 2\nc = 3\n".parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.swap("a", "c"));
+    /// assert_eq!(doc.to_string(), "c = 3\nb = 2\na = 1\n");
+    /// assert!(!doc.swap("a", "missing"));
+    /// # }
+    /// ```
+    pub fn swap(&mut self, key_a: &str, key_b: &str) -> bool {
+        let (a, b) = match (
+            self.items.get_index_of(key_a),
+            self.items.get_index_of(key_b),
+        ) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+        let first = self.first_value_key();
+        self.items.swap_indices(a, b);
+        self.rebalance_first_decor(first);
+        true
+    }
+
+    /// The key of the first key/value pair to be rendered
+    fn first_value_key(&self) -> Option<InternalString> {
+        self.items
+            .iter()
+            .find(|(_, kv)| {
+                kv.value.is_value() || kv.value.as_table().map_or(false, Table::is_dotted)
+            })
+            .map(|(key, _)| key.clone())
+    }
+
+    /// Keep the blank lines before the first key/value pair in place after it has been moved
+    fn rebalance_first_decor(&mut self, old_first: Option<InternalString>) {
+        if self.is_dotted() {
+            return;
+        }
+        let (old_first, new_first) = match (old_first, self.first_value_key()) {
+            (Some(old_first), Some(new_first)) if old_first != new_first => (old_first, new_first),
+            _ => return,
+        };
+        let prefix = |table: &Self, key: &str| {
+            table
+                .items
+                .get(key)
+                .and_then(|kv| kv.key.decor.prefix())
+                .and_then(RawString::as_str)
+                .unwrap_or("")
+                .to_owned()
+        };
+        let old_prefix = prefix(self, &old_first);
+        let new_prefix = prefix(self, &new_first);
+        let (old_blank, old_rest) = split_blank_lines(&old_prefix);
+        let (new_blank, new_rest) = split_blank_lines(&new_prefix);
+        if old_blank == new_blank {
+            return;
+        }
+        if let Some(kv) = self.items.get_mut(&old_first) {
+            kv.key
+                .decor
+                .set_prefix(format!("{}{}", new_blank, old_rest));
+        }
+        if let Some(kv) = self.items.get_mut(&new_first) {
+            kv.key
+                .decor
+                .set_prefix(format!("{}{}", old_blank, new_rest));
+        }
+    }
+
     /// If a table has no key/value pairs and implicit, it will not be displayed.
     ///
     /// # Examples
@@ -229,7 +337,7 @@ impl Table {
     }
 
     /// Returns the location within the original document
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         self.span.clone()
     }
 
@@ -264,6 +372,33 @@ impl Table {
         )
     }
 
+    /// Panics unless the table holds exactly the keys in `expected`, in that order.
+    ///
+    /// Every entry must also be stored under its key's name.  Order is the insertion order:
+    /// [`Table::insert`] keeps an existing key in place, [`Table::remove`] shifts the following
+    /// entries up, and a clone keeps the order of the original.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut table = toml_edit::Table::new();
+    /// table.insert("b", toml_edit::value(1));
+    /// table.insert("a", toml_edit::value(2));
+    /// table.assert_order(&["b", "a"]);
+    /// ```
+    pub fn assert_order(&self, expected: &[&str]) {
+        for (name, kv) in self.items.iter() {
+            assert_eq!(
+                name.as_str(),
+                kv.key.get(),
+                "entry `{}` is stored under `{}`",
+                kv.key.get(),
+                name
+            );
+        }
+        let actual = self.iter().map(|(key, _)| key).collect::<Vec<_>>();
+        assert_eq!(actual, expected, "table keys are out of order");
+    }
+
     /// Returns the number of non-empty items in the table.
     pub fn len(&self) -> usize {
         self.items.iter().filter(|i| !(i.1).value.is_none()).count()
@@ -279,6 +414,68 @@ impl Table {
         self.items.clear()
     }
 
+    /// Replaces the key/value pairs with those of `other`.
+    ///
+    /// Unlike `*self = other`, this keeps the header's decor, position, and whether it is
+    /// implicit.
+    pub fn replace_contents(&mut self, other: Table) {
+        self.items = other.items;
+    }
+
+    /// Merges the key/value pairs of `other` into this table.
+    ///
+    /// Tables and inline tables present in both are merged recursively; `strategy` decides how
+    /// any other key present in both is resolved.  Keys only in `other` are copied over.  Tables
+    /// and arrays of tables merged into an inline table become inline tables and arrays.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// # #[cfg(feature = "display")] {
+    /// use toml_edit::{Document, MergeStrategy};
+    ///
+    /// let mut base = "a = 1\nlist = [1]\n[t]\nx = 1\n".parse::<Document>().unwrap();
+    /// let layer = "a = 2\nlist = [2]\n[t]\ny = 2\n".parse::<Document>().unwrap();
+    /// base.merge_with(&layer, MergeStrategy::AppendArrays);
+    /// assert_eq!(base.to_string(), "a = 2\nlist = [1, 2]\n[t]\nx = 1\ny = 2\n");
+    /// # }
+    /// # }
+    /// ```
+    pub fn merge_with(&mut self, other: &Table, strategy: MergeStrategy) {
+        merge_table_like(self, other, MergePolicy::Strategy(strategy));
+    }
+
+    /// Renames the key `old` to `new`, keeping its position and surrounding whitespace.
+    ///
+    /// The raw form of `new` is used if it has one, like a key parsed from `"'new'"`; otherwise
+    /// the default one is used.  Returns `false`, leaving the table unchanged, if `old` is missing
+    /// or `new` is already taken by another key.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "a = 1 # one\n\"b c\" = 2\n".parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.rename_key("a", &toml_edit::Key::new("x")));
+    /// assert!(doc.rename_key("b c", &"'y z'".parse::<toml_edit::Key>().unwrap()));
+    /// assert_eq!(doc.to_string(), "x = 1 # one\n'y z' = 2\n");
+    /// # }
+    /// ```
+    pub fn rename_key(&mut self, old: &str, new: &Key) -> bool {
+        let index = match self.items.get_index_of(old) {
+            Some(index) => index,
+            None => return false,
+        };
+        if old != new.get() && self.items.contains_key(new.get()) {
+            return false;
+        }
+        let (_, mut kv) = self.items.shift_remove_index(index).unwrap();
+        let decor = core::mem::take(&mut kv.key.decor);
+        kv.key = new.clone().with_decor(decor);
+        let (new_index, _) = self.items.insert_full(new.get().into(), kv);
+        self.items.move_index(new_index, index);
+        true
+    }
+
     /// Gets the given key's corresponding entry in the Table for in-place manipulation.
     pub fn entry<'a>(&'a mut self, key: &str) -> Entry<'a> {
         // Accept a `&str` rather than an owned type to keep `InternalString`, well, internal
@@ -300,7 +497,33 @@ impl Table {
         }
     }
 
+    /// Parses `key` as written in TOML, bare or quoted, and gets its entry like
+    /// [`Table::entry_format`].
+    ///
+    /// Unlike [`Table::entry`], which accepts any name, empty and dotted keys like `""`, `"."`
+    /// or `"a.b"` are rejected, see [`parse_key`][crate::parse_key].  Quote a key to use such a
+    /// name.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut table = toml_edit::Table::new();
+    /// assert!(table.try_entry("").is_err());
+    /// assert!(table.try_entry("a.b").is_err());
+    /// table.try_entry("'a.b'").unwrap().or_insert(toml_edit::value(1));
+    /// assert_eq!(table.to_string(), "'a.b' = 1\n");
+    /// # }
+    /// ```
+    #[cfg(feature = "parse")]
+    pub fn try_entry<'a>(&'a mut self, key: &str) -> Result<Entry<'a>, crate::InvalidKey> {
+        let key = crate::parse_key(key)?;
+        Ok(self.entry_format(&key))
+    }
+
     /// Returns an optional reference to an item given the key.
+    ///
+    /// `key` is the key's name after unquoting and unescaping, like [`Key::get`], and is looked
+    /// up as is, without being parsed: `"a.b"` names a single key containing a dot.
     pub fn get<'a>(&'a self, key: &str) -> Option<&'a Item> {
         self.items.get(key).and_then(|kv| {
             if !kv.value.is_none() {
@@ -312,6 +535,9 @@ impl Table {
     }
 
     /// Returns an optional mutable reference to an item given the key.
+    ///
+    /// Unlike [`Table::entry`] or indexing with `table[key]`, a missing key leaves the table
+    /// untouched.
     pub fn get_mut<'a>(&'a mut self, key: &str) -> Option<&'a mut Item> {
         self.items.get_mut(key).and_then(|kv| {
             if !kv.value.is_none() {
@@ -322,6 +548,26 @@ impl Table {
         })
     }
 
+    /// Returns an optional reference to a table given the key.
+    pub fn get_table<'a>(&'a self, key: &str) -> Option<&'a Table> {
+        self.get(key).and_then(Item::as_table)
+    }
+
+    /// Returns an optional mutable reference to a table given the key.
+    pub fn get_table_mut<'a>(&'a mut self, key: &str) -> Option<&'a mut Table> {
+        self.get_mut(key).and_then(Item::as_table_mut)
+    }
+
+    /// Returns an optional reference to an array of tables given the key.
+    pub fn get_array_of_tables<'a>(&'a self, key: &str) -> Option<&'a ArrayOfTables> {
+        self.get(key).and_then(Item::as_array_of_tables)
+    }
+
+    /// Returns an optional reference to a value given the key.
+    pub fn get_value<'a>(&'a self, key: &str) -> Option<&'a Value> {
+        self.get(key).and_then(Item::as_value)
+    }
+
     /// Return references to the key-value pair stored for key, if it is present, else None.
     pub fn get_key_value<'a>(&'a self, key: &str) -> Option<(&'a Key, &'a Item)> {
         self.items.get(key).and_then(|kv| {
@@ -353,6 +599,33 @@ impl Table {
         }
     }
 
+    /// Returns true if the table contains an item at `path`, descending through tables and inline
+    /// tables.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "parse")] {
+    /// let doc = "[a]\nb = { c = 1 }\n".parse::<toml_edit::Document>().unwrap();
+    /// assert!(doc.contains_path(&["a", "b", "c"]));
+    /// assert!(!doc.contains_path(&["a", "b", "c", "d"]));
+    /// assert!(!doc.contains_path(&["a", "x"]));
+    /// # }
+    /// ```
+    pub fn contains_path(&self, path: &[&str]) -> bool {
+        let (last, parents) = match path.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        let mut table: &dyn TableLike = self;
+        for key in parents {
+            match table.get(key).and_then(Item::as_table_like) {
+                Some(t) => table = t,
+                None => return false,
+            }
+        }
+        table.contains_key(last)
+    }
+
     /// Returns true if the table contains a table with the given key.
     pub fn contains_table(&self, key: &str) -> bool {
         if let Some(kv) = self.items.get(key) {
@@ -386,17 +659,128 @@ impl Table {
         self.items.insert(key.into(), kv).map(|kv| kv.value)
     }
 
+    /// Inserts a key-value pair, returning the table for further chaining.
+    ///
+    /// # Example
+    /// ```rust
+    /// use toml_edit::{value, Table};
+    ///
+    /// let table = Table::new().with("a", value(1)).with("b", value(2));
+    /// assert_eq!(table.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn with(mut self, key: &str, item: Item) -> Self {
+        self.insert(key, item);
+        self
+    }
+
+    /// Inserts a key-value pair before all other entries.
+    ///
+    /// An existing entry for `key` is replaced and moved to the front.  The blank lines that
+    /// separated the old first key/value pair from the header are moved to the new one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut doc = "[package]\nversion = \"1.0\"\n".parse::<toml_edit::Document>().unwrap();
+    /// let package = doc["package"].as_table_mut().unwrap();
+    /// package.prepend("name", toml_edit::value("foo"));
+    /// assert_eq!(doc.to_string(), "[package]\nname = \"foo\"\nversion = \"1.0\"\n");
+    /// # }
+    /// ```
+    pub fn prepend(&mut self, key: &str, item: Item) -> Option<Item> {
+        let old_first = self.first_value_key();
+        let kv = TableKeyValue::new(Key::new(key), item);
+        let (index, old) = self.items.insert_full(key.into(), kv);
+        self.items.move_index(index, 0);
+        self.rebalance_first_decor(old_first);
+        old.map(|kv| kv.value)
+    }
+
     /// Inserts a key-value pair into the map.
     pub fn insert_formatted(&mut self, key: &Key, item: Item) -> Option<Item> {
         let kv = TableKeyValue::new(key.to_owned(), item);
         self.items.insert(key.get().into(), kv).map(|kv| kv.value)
     }
 
+    /// Inserts an item under a dotted key, like `a.b.c = 1`.
+    ///
+    /// Each `Key` in `path` is a segment, see [dotted keys](Key#dotted-keys).  Missing
+    /// intermediate tables are created as implicit and [dotted][Table::set_dotted].
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// # #[cfg(feature = "parse")] {
+    /// let mut table = toml_edit::Table::new();
+    /// let path = toml_edit::Key::path(&["a", "b", "c"]);
+    /// table.insert_dotted(&path, toml_edit::value(1));
+    /// assert_eq!(table.to_string(), "a.b.c = 1\n");
+    /// # }
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `path` is empty or an intermediate segment is not a table.
+    pub fn insert_dotted(&mut self, path: &[Key], item: Item) -> Option<Item> {
+        let (leaf, parents) = path.split_last().expect("dotted key must not be empty");
+        let mut table = self;
+        for key in parents {
+            let entry = table.entry_format(key).or_insert(Item::None);
+            if entry.is_none() {
+                let mut new_table = Table::new();
+                new_table.set_implicit(true);
+                new_table.set_dotted(true);
+                *entry = Item::Table(new_table);
+            }
+            table = match entry {
+                Item::Table(table) => table,
+                other => panic!(
+                    "cannot extend `{}` ({}) with a dotted key",
+                    key.get(),
+                    other.type_name()
+                ),
+            };
+        }
+        table.insert_formatted(leaf, item)
+    }
+
+    /// Inserts an empty table under `key`, returning it for further building.
+    ///
+    /// # Example
+    /// ```rust
+    /// let mut doc = toml_edit::Document::new();
+    /// doc.append_table("db").append_table("pool")["size"] = toml_edit::value(4);
+    /// assert_eq!(doc["db"]["pool"]["size"].as_integer(), Some(4));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `key` is already present.
+    pub fn append_table(&mut self, key: &str) -> &mut Table {
+        let item = self.entry(key).or_insert(Item::None);
+        assert!(item.is_none(), "key `{}` is already present", key);
+        *item = Item::Table(Table::new());
+        item.as_table_mut().expect("just inserted a table")
+    }
+
     /// Removes an item given the key.
+    ///
+    /// Like [`Vec::remove`], the following entries are shifted down to preserve the order of the
+    /// table, making this *O(n)*.
     pub fn remove(&mut self, key: &str) -> Option<Item> {
         self.items.shift_remove(key).map(|kv| kv.value)
     }
 
+    /// Removes an item given the key, without preserving order.
+    ///
+    /// Like [`Vec::swap_remove`], the last entry takes the place of the removed one, making this
+    /// *O(1)*.
+    pub fn swap_remove(&mut self, key: &str) -> Option<Item> {
+        self.items.swap_remove(key).map(|kv| kv.value)
+    }
+
     /// Removes a key from the map, returning the stored key and value if the key was previously in the map.
     pub fn remove_entry(&mut self, key: &str) -> Option<(Key, Item)> {
         self.items.shift_remove(key).map(|kv| (kv.key, kv.value))
@@ -418,8 +802,8 @@ impl Table {
 }
 
 #[cfg(feature = "display")]
-impl std::fmt::Display for Table {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Table {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         use crate::encode::Encode;
         let children = self.get_values();
         // print table body
@@ -460,7 +844,12 @@ impl IntoIterator for Table {
     type IntoIter = IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        Box::new(self.items.into_iter().map(|(k, kv)| (k, kv.value)))
+        Box::new(
+            self.items
+                .into_iter()
+                .filter(|(_, kv)| !kv.value.is_none())
+                .map(|(k, kv)| (k, kv.value)),
+        )
     }
 }
 
@@ -473,7 +862,38 @@ impl<'s> IntoIterator for &'s Table {
     }
 }
 
-pub(crate) type KeyValuePairs = IndexMap<InternalString, TableKeyValue>;
+pub(crate) type KeyValuePairs = IndexMap<InternalString, TableKeyValue, RandomState>;
+
+/// The hasher of [`KeyValuePairs`], randomly keyed when `std` is available
+#[cfg(feature = "std")]
+pub(crate) type RandomState = std::collections::hash_map::RandomState;
+/// The hasher of [`KeyValuePairs`], randomly keyed when `std` is available
+#[cfg(not(feature = "std"))]
+pub(crate) type RandomState = core::hash::BuildHasherDefault<FnvHasher>;
+
+/// Unkeyed FNV-1a, as `no_std` has no source of randomness
+#[cfg(not(feature = "std"))]
+pub(crate) struct FnvHasher(u64);
+
+#[cfg(not(feature = "std"))]
+impl Default for FnvHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::hash::Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
 
 fn decorate_table(table: &mut Table) {
     for (key_decor, value) in table
@@ -630,6 +1050,81 @@ impl TableLike for Table {
     }
 }
 
+/// How [`Table::merge_with`] resolves a key present in both tables
+///
+/// Tables are always merged recursively, whatever the strategy.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MergeStrategy {
+    /// Replace the existing item with the other one
+    #[default]
+    Override,
+    /// Keep the existing item
+    KeepExisting,
+    /// Concatenate arrays and arrays of tables, replacing anything else
+    AppendArrays,
+}
+
+/// A [`MergeStrategy`], or the policy of [`Item::merge`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum MergePolicy {
+    Strategy(MergeStrategy),
+    /// Concatenate arrays of tables, replacing anything else, including arrays
+    AppendArraysOfTables,
+}
+
+fn merge_table_like(target: &mut dyn TableLike, other: &dyn TableLike, policy: MergePolicy) {
+    for (key, item) in other.iter() {
+        match target.get_mut(key) {
+            Some(existing) => merge_item(existing, item, policy, false),
+            None => {
+                target.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+// `inline` items can only hold values, so tables and arrays of tables get converted
+pub(crate) fn merge_item(existing: &mut Item, other: &Item, policy: MergePolicy, inline: bool) {
+    if let Some(other) = other.as_table_like() {
+        match existing {
+            Item::Value(Value::InlineTable(target)) => {
+                target.merge_table_like(other, policy);
+                return;
+            }
+            Item::Table(target) => {
+                merge_table_like(target, other, policy);
+                return;
+            }
+            _ => {}
+        }
+    }
+    match (policy, existing, other) {
+        (MergePolicy::Strategy(MergeStrategy::KeepExisting), _, _) => {}
+        (
+            MergePolicy::Strategy(MergeStrategy::AppendArrays),
+            Item::Value(Value::Array(existing)),
+            Item::Value(Value::Array(other)),
+        ) => {
+            existing.append(&mut other.clone());
+        }
+        (
+            MergePolicy::Strategy(MergeStrategy::AppendArrays) | MergePolicy::AppendArraysOfTables,
+            Item::ArrayOfTables(existing),
+            Item::ArrayOfTables(other),
+        ) => {
+            for table in other.iter() {
+                existing.push(table.clone());
+            }
+        }
+        (_, existing, other) => {
+            *existing = other.clone();
+            if inline {
+                existing.make_value();
+            }
+        }
+    }
+}
+
 /// A view into a single location in a map, which may be vacant or occupied.
 pub enum Entry<'a> {
     /// An occupied Entry.
@@ -657,6 +1152,18 @@ impl<'a> Entry<'a> {
         }
     }
 
+    /// Provides in-place mutable access to an occupied entry before any potential inserts
+    /// into the map.
+    pub fn and_modify<F: FnOnce(&mut Item)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
     /// Ensures a value is in the entry by inserting the default if empty, and returns
     /// a mutable reference to the value in the entry.
     pub fn or_insert(self, default: Item) -> &'a mut Item {
@@ -720,7 +1227,7 @@ impl<'a> OccupiedEntry<'a> {
 
     /// Sets the value of the entry, and returns the entry's old value
     pub fn insert(&mut self, mut value: Item) -> Item {
-        std::mem::swap(&mut value, &mut self.entry.get_mut().value);
+        core::mem::swap(&mut value, &mut self.entry.get_mut().value);
         value
     }
 
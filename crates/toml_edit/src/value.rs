@@ -1,9 +1,11 @@
-use std::iter::FromIterator;
-use std::str::FromStr;
+use core::iter::FromIterator;
+use core::str::FromStr;
+use core::time::Duration;
 
 use toml_datetime::*;
 
 use crate::key::Key;
+use crate::prelude::*;
 use crate::repr::{Decor, Formatted};
 use crate::{Array, InlineTable, InternalString, RawString};
 
@@ -26,21 +28,300 @@ pub enum Value {
     InlineTable(InlineTable),
 }
 
+/// How a [`Datetime`] is written, see [`Value::set_datetime_with`]
+///
+/// TOML accepts both `1979-05-27T07:32:00Z` and `1979-05-27 07:32:00z`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DatetimeFormat {
+    /// Separate the date and time with a space rather than `T`
+    pub space_separator: bool,
+    /// Write a UTC offset as `z` rather than `Z`
+    pub lowercase_z: bool,
+}
+
+impl DatetimeFormat {
+    /// The format of a datetime's raw representation
+    fn detect(raw: &str) -> Self {
+        Self {
+            space_separator: raw.as_bytes().get(10) == Some(&b' '),
+            lowercase_z: raw.ends_with('z'),
+        }
+    }
+}
+
+/// How strings are quoted, see [`Document::set_string_quote_style`][crate::Document::set_string_quote_style]
+///
+/// New strings only become literal strings to avoid escaping `\`; parsed strings keep their
+/// quotes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StringQuoteStyle {
+    /// Keep each string as it is
+    #[default]
+    Preserve,
+    /// Use a literal string, e.g. `'C:\temp'` or `'say "hi"'`, when it avoids escapes
+    Minimal,
+    /// Use a basic string, e.g. `"C:\\temp"`, for every string
+    AlwaysBasic,
+}
+
+/// The type of a [`Value`], see [`Value::kind`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    /// A string value.
+    String,
+    /// A 64-bit integer value.
+    Integer,
+    /// A 64-bit float value.
+    Float,
+    /// A boolean value.
+    Boolean,
+    /// An RFC 3339 formatted date-time with offset.
+    Datetime,
+    /// An inline array of values.
+    Array,
+    /// An inline table of key/value pairs.
+    InlineTable,
+}
+
+impl ValueKind {
+    /// Text description of value type, as in [`Value::type_name`]
+    pub fn type_name(self) -> &'static str {
+        match self {
+            ValueKind::String => "string",
+            ValueKind::Integer => "integer",
+            ValueKind::Float => "float",
+            ValueKind::Boolean => "boolean",
+            ValueKind::Datetime => "datetime",
+            ValueKind::Array => "array",
+            ValueKind::InlineTable => "inline table",
+        }
+    }
+}
+
+impl core::fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.type_name().fmt(f)
+    }
+}
+
+/// A value could not be converted by [`Value::coerce_to`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoerceError {
+    from: ValueKind,
+    to: ValueKind,
+}
+
+impl CoerceError {
+    /// The type of the value that was converted
+    pub fn from_kind(&self) -> ValueKind {
+        self.from
+    }
+
+    /// The type the value was to be converted to
+    pub fn to_kind(&self) -> ValueKind {
+        self.to
+    }
+}
+
+impl core::fmt::Display for CoerceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "cannot coerce {} to {}", self.from, self.to)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CoerceError {}
+
+/// Constructors
+///
+/// These avoid the type inference ambiguity of `Into<Value>`, e.g. between integers and floats.
+/// The value is rendered with its default representation.
+impl Value {
+    /// Create an integer value.
+    pub fn integer(i: i64) -> Self {
+        Value::from(i)
+    }
+
+    /// Create a float value.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let v = toml_edit::Value::float(3.0);
+    /// assert_eq!(v.to_string(), "3.0");
+    /// # }
+    /// ```
+    pub fn float(f: f64) -> Self {
+        Value::from(f)
+    }
+
+    /// Create a boolean value.
+    pub fn boolean(b: bool) -> Self {
+        Value::from(b)
+    }
+
+    /// Create a boolean value from its TOML spelling.
+    ///
+    /// Only `true` and `false` are booleans in TOML; anything else, including `1`, `0` or
+    /// `True`, is rejected.
+    ///
+    /// # Example
+    /// ```rust
+    /// assert_eq!(toml_edit::Value::parse_bool("true").and_then(|v| v.as_bool()), Some(true));
+    /// assert!(toml_edit::Value::parse_bool("1").is_none());
+    /// ```
+    pub fn parse_bool(s: &str) -> Option<Self> {
+        match s {
+            "true" => Some(Value::boolean(true)),
+            "false" => Some(Value::boolean(false)),
+            _ => None,
+        }
+    }
+
+    /// Create a basic string value.
+    pub fn string(s: &str) -> Self {
+        Value::from(s)
+    }
+
+    /// Create a date-time value.
+    pub fn datetime(d: impl Into<Datetime>) -> Self {
+        Value::from(d.into())
+    }
+}
+
 /// Downcasting
 impl Value {
     /// Text description of value type
     pub fn type_name(&self) -> &'static str {
+        self.kind().type_name()
+    }
+
+    /// The type of value
+    pub fn kind(&self) -> ValueKind {
         match self {
-            Value::String(..) => "string",
-            Value::Integer(..) => "integer",
-            Value::Float(..) => "float",
-            Value::Boolean(..) => "boolean",
-            Value::Datetime(..) => "datetime",
-            Value::Array(..) => "array",
-            Value::InlineTable(..) => "inline table",
+            Value::String(..) => ValueKind::String,
+            Value::Integer(..) => ValueKind::Integer,
+            Value::Float(..) => ValueKind::Float,
+            Value::Boolean(..) => ValueKind::Boolean,
+            Value::Datetime(..) => ValueKind::Datetime,
+            Value::Array(..) => ValueKind::Array,
+            Value::InlineTable(..) => ValueKind::InlineTable,
         }
     }
 
+    /// The number of characters of a string, or `None` if `self` is not a string.
+    ///
+    /// Characters are Unicode scalar values, so `"né"` has 2 whatever its encoding.
+    pub fn char_len(&self) -> Option<usize> {
+        self.as_str().map(|s| s.chars().count())
+    }
+
+    /// The number of elements of an array or pairs of an inline table, or `None` for any other
+    /// value.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Value::Array(array) => Some(array.len()),
+            Value::InlineTable(table) => Some(table.len()),
+            _ => None,
+        }
+    }
+
+    /// Whether `self` is an empty array or inline table.
+    ///
+    /// Any other value has no elements to speak of, so it is not considered empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == Some(0)
+    }
+
+    /// Converts `self` to a value of type `target`, refusing conversions that lose information
+    ///
+    /// A value of type `target` is returned as is, and any scalar can be converted to a string.
+    /// Otherwise only these conversions succeed:
+    /// - A string holding an integer, a finite float, `true` or `false`, or a date-time, without
+    ///   surrounding whitespace, converts to that type.
+    /// - An integer converts to a float if the float holds it exactly, i.e. up to 2<sup>53</sup>.
+    /// - A float converts to an integer if it has no fractional part and is in range.
+    ///
+    /// Integers never convert to booleans, see [`Value::coerce_to_lenient`].
+    ///
+    /// The result keeps the decor of `self`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use toml_edit::{Value, ValueKind};
+    ///
+    /// let port = Value::from("8080").coerce_to(ValueKind::Integer).unwrap();
+    /// assert_eq!(port.as_integer(), Some(8080));
+    /// assert!(Value::from(1.5).coerce_to(ValueKind::Integer).is_err());
+    /// ```
+    pub fn coerce_to(&self, target: ValueKind) -> Result<Value, CoerceError> {
+        self.coerce(target, false)
+    }
+
+    /// Converts `self` to a value of type `target` like [`Value::coerce_to`], and also converts
+    /// the integers `0` and `1` to `false` and `true`
+    ///
+    /// # Example
+    /// ```rust
+    /// use toml_edit::{Value, ValueKind};
+    ///
+    /// assert!(Value::from(1).coerce_to(ValueKind::Boolean).is_err());
+    /// let enabled = Value::from(1).coerce_to_lenient(ValueKind::Boolean).unwrap();
+    /// assert_eq!(enabled.as_bool(), Some(true));
+    /// ```
+    pub fn coerce_to_lenient(&self, target: ValueKind) -> Result<Value, CoerceError> {
+        self.coerce(target, true)
+    }
+
+    fn coerce(&self, target: ValueKind, lenient: bool) -> Result<Value, CoerceError> {
+        const MAX_EXACT_FLOAT_INT: i64 = 1 << f64::MANTISSA_DIGITS;
+
+        let coerced = match (self, target) {
+            (_, target) if self.kind() == target => Some(self.clone()),
+            (Value::String(s), ValueKind::Integer) => {
+                s.value().parse::<i64>().ok().map(Value::from)
+            }
+            (Value::String(s), ValueKind::Float) => s
+                .value()
+                .parse::<f64>()
+                .ok()
+                .filter(|f| f.is_finite())
+                .map(Value::from),
+            (Value::String(s), ValueKind::Boolean) => Value::parse_bool(s.value()),
+            (Value::String(s), ValueKind::Datetime) => {
+                s.value().parse::<Datetime>().ok().map(Value::from)
+            }
+            (Value::Integer(i), ValueKind::String) => Some(Value::from(i.value().to_string())),
+            (Value::Integer(i), ValueKind::Float) => {
+                let i = *i.value();
+                (-MAX_EXACT_FLOAT_INT..=MAX_EXACT_FLOAT_INT)
+                    .contains(&i)
+                    .then(|| Value::from(i as f64))
+            }
+            (Value::Integer(i), ValueKind::Boolean) if lenient => match *i.value() {
+                0 => Some(Value::from(false)),
+                1 => Some(Value::from(true)),
+                _ => None,
+            },
+            (Value::Float(f), ValueKind::String) => Some(Value::from(f.value().to_string())),
+            (Value::Float(f), ValueKind::Integer) => {
+                let f = *f.value();
+                // `i64::MAX as f64` rounds up to 2^63, which is out of range
+                let in_range = (i64::MIN as f64..i64::MAX as f64).contains(&f);
+                (in_range && f as i64 as f64 == f).then(|| Value::from(f as i64))
+            }
+            (Value::Boolean(b), ValueKind::String) => Some(Value::from(b.value().to_string())),
+            (Value::Datetime(dt), ValueKind::String) => Some(Value::from(dt.value().to_string())),
+            _ => None,
+        };
+        let mut coerced = coerced.ok_or(CoerceError {
+            from: self.kind(),
+            to: target,
+        })?;
+        *coerced.decor_mut() = self.decor().clone();
+        Ok(coerced)
+    }
+
     /// Casts `self` to str.
     pub fn as_str(&self) -> Option<&str> {
         match *self {
@@ -54,6 +335,90 @@ impl Value {
         self.as_str().is_some()
     }
 
+    /// Replaces the contents of a string, returning `false` if `self` is not a string.
+    ///
+    /// The decor is preserved, as is whether this is a basic or literal string when the new
+    /// contents can be represented that way.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut v = "'old'".parse::<toml_edit::Value>().unwrap();
+    /// assert!(v.set_str("new"));
+    /// assert_eq!(v.to_string(), "'new'");
+    /// # }
+    /// ```
+    pub fn set_str(&mut self, s: &str) -> bool {
+        match self {
+            Value::String(formatted) => {
+                let mut value = Formatted::new(s.to_owned());
+                #[cfg(feature = "display")]
+                {
+                    let literal = formatted
+                        .as_repr()
+                        .and_then(|r| r.as_raw().as_str())
+                        .map_or(false, |raw| raw.starts_with('\''));
+                    let repr = literal
+                        .then(|| crate::encode::to_literal_string_repr(s))
+                        .flatten()
+                        .unwrap_or_else(|| crate::encode::to_string_repr(s, None, Some(false)));
+                    value.set_repr_unchecked(repr);
+                }
+                *value.decor_mut() = formatted.decor().clone();
+                *formatted = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Replaces a datetime, returning `false` if `self` is not a datetime.
+    ///
+    /// The decor is preserved, as is the [`DatetimeFormat`] of the original.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let mut v = "1979-05-27 07:32:00Z".parse::<toml_edit::Value>().unwrap();
+    /// let dt = "2000-01-01T00:00:00Z".parse::<toml_edit::Datetime>().unwrap();
+    /// assert!(v.set_datetime(dt));
+    /// assert_eq!(v.to_string(), "2000-01-01 00:00:00Z");
+    /// # }
+    /// ```
+    pub fn set_datetime(&mut self, dt: impl Into<Datetime>) -> bool {
+        let format = match self {
+            Value::Datetime(formatted) => formatted
+                .as_repr()
+                .and_then(|r| r.as_raw().as_str())
+                .map(DatetimeFormat::detect)
+                .unwrap_or_default(),
+            _ => return false,
+        };
+        self.set_datetime_with(dt, format)
+    }
+
+    /// Replaces a datetime, written with `format`, returning `false` if `self` is not a datetime.
+    ///
+    /// The decor is preserved.
+    pub fn set_datetime_with(&mut self, dt: impl Into<Datetime>, format: DatetimeFormat) -> bool {
+        match self {
+            Value::Datetime(formatted) => {
+                let dt = dt.into();
+                #[cfg(feature = "display")]
+                let repr = crate::encode::to_datetime_repr(&dt, format);
+                #[cfg(not(feature = "display"))]
+                let _ = format;
+                let mut value = Formatted::new(dt);
+                #[cfg(feature = "display")]
+                value.set_repr_unchecked(repr);
+                *value.decor_mut() = formatted.decor().clone();
+                *formatted = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Casts `self` to integer.
     pub fn as_integer(&self) -> Option<i64> {
         match *self {
@@ -62,6 +427,26 @@ impl Value {
         }
     }
 
+    /// Casts `self` to an unsigned integer.
+    ///
+    /// This is `None` for negative integers.  TOML integers are signed 64-bit, so integers above
+    /// `i64::MAX`, like `18446744073709551615`, fail to parse unless
+    /// [`ParseOptions::u64_integers`][crate::ParseOptions::u64_integers] is set; this then reads
+    /// them back from their raw representation.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Integer(f) => {
+                let i = *f.value();
+                u64::try_from(i).ok().or_else(|| {
+                    // Only a `u64` literal, kept as its `i64` bits, has no sign for a negative value
+                    let raw = f.as_repr()?.as_raw().as_str()?;
+                    (!raw.starts_with('-')).then_some(i as u64)
+                })
+            }
+            _ => None,
+        }
+    }
+
     /// Returns true iff `self` is an integer.
     pub fn is_integer(&self) -> bool {
         self.as_integer().is_some()
@@ -106,6 +491,20 @@ impl Value {
         self.as_datetime().is_some()
     }
 
+    /// Casts `self` to a duration, reading an integer or float as a number of seconds.
+    ///
+    /// Fractional seconds are kept down to the nanosecond.  Negative, infinite and NaN numbers,
+    /// and numbers too large for a [`Duration`], give `None`.
+    pub fn as_duration(&self) -> Option<Duration> {
+        match *self {
+            Value::Integer(ref value) => {
+                u64::try_from(*value.value()).ok().map(Duration::from_secs)
+            }
+            Value::Float(ref value) => Duration::try_from_secs_f64(*value.value()).ok(),
+            _ => None,
+        }
+    }
+
     /// Casts `self` to array.
     pub fn as_array(&self) -> Option<&Array> {
         match *self {
@@ -127,6 +526,18 @@ impl Value {
         self.as_array().is_some()
     }
 
+    /// Returns true iff `self` is a non-empty array whose elements are all inline tables, like
+    /// `x = [{ a = 1 }, { a = 2 }]`.
+    ///
+    /// This is the inline spelling of an array of tables; the `[[x]]` header spelling is an
+    /// [`Item::ArrayOfTables`][crate::Item::ArrayOfTables] instead, see
+    /// [`Item::is_array_of_tables`][crate::Item::is_array_of_tables].
+    pub fn is_array_of_inline_tables(&self) -> bool {
+        self.as_array().map_or(false, |a| {
+            !a.is_empty() && a.iter().all(|v| v.is_inline_table())
+        })
+    }
+
     /// Casts `self` to inline table.
     pub fn as_inline_table(&self) -> Option<&InlineTable> {
         match *self {
@@ -147,6 +558,22 @@ impl Value {
     pub fn is_inline_table(&self) -> bool {
         self.as_inline_table().is_some()
     }
+
+    /// Casts `self` to array, returning `self` unchanged if it is not one.
+    pub fn into_array(self) -> Result<Array, Self> {
+        match self {
+            Value::Array(a) => Ok(a),
+            _ => Err(self),
+        }
+    }
+
+    /// Casts `self` to inline table, returning `self` unchanged if it is not one.
+    pub fn into_inline_table(self) -> Result<InlineTable, Self> {
+        match self {
+            Value::InlineTable(t) => Ok(t),
+            _ => Err(self),
+        }
+    }
 }
 
 impl Value {
@@ -201,13 +628,65 @@ impl Value {
         self
     }
 
+    /// Sets the suffix to a `# text` comment after the value.
+    ///
+    /// `text` should be a single line.  Within an array, the comma is written before the comment
+    /// and a newline after it.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let mut arr = toml_edit::Array::new();
+    /// arr.push_formatted(toml_edit::Value::from(1).with_comment("one"));
+    /// arr.push_formatted(toml_edit::Value::from(2));
+    /// assert_eq!(arr.to_string(), "[1, # one\n 2]");
+    /// # }
+    /// ```
+    pub fn with_comment(mut self, text: &str) -> Self {
+        self.decor_mut().set_suffix(format!(" # {text}"));
+        self
+    }
+
+    /// Clones the value without its decor, ready to be decorated for a new location
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "display")] {
+    /// let v = toml_edit::Value::from(42).decorated(" ", " # answer");
+    /// assert_eq!(&v.clone_bare().to_string(), "42");
+    /// # }
+    /// ```
+    pub fn clone_bare(&self) -> Self {
+        let mut value = self.clone();
+        value.decor_mut().clear();
+        value
+    }
+
+    /// Renders the value on a single line, without comments or its original formatting
+    ///
+    /// Arrays and inline tables are laid out like newly created ones, and multi-line strings
+    /// are written with escaped newlines, e.g. for embedding the value in a log message.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(all(feature = "parse", feature = "display"))] {
+    /// let v = "[\n  1,\n  2, # two\n  3,\n]".parse::<toml_edit::Value>().unwrap();
+    /// assert_eq!(v.to_string_compact(), "[1, 2, 3]");
+    /// # }
+    /// ```
+    #[cfg(feature = "display")]
+    pub fn to_string_compact(&self) -> String {
+        let mut value = self.clone_bare();
+        compact(&mut value);
+        value.to_string()
+    }
+
     pub(crate) fn decorate(&mut self, prefix: impl Into<RawString>, suffix: impl Into<RawString>) {
         let decor = self.decor_mut();
         *decor = Decor::new(prefix, suffix);
     }
 
     /// Returns the location within the original document
-    pub(crate) fn span(&self) -> Option<std::ops::Range<usize>> {
+    pub(crate) fn span(&self) -> Option<core::ops::Range<usize>> {
         match self {
             Value::String(f) => f.span(),
             Value::Integer(f) => f.span(),
@@ -291,6 +770,16 @@ impl From<f64> for Value {
     }
 }
 
+impl From<Duration> for Value {
+    /// Stores the duration as a float of seconds, see [`Value::as_duration`].
+    ///
+    /// Nanoseconds are exact for durations up to about 104 days, longer ones are rounded to the
+    /// precision of an `f64`.
+    fn from(d: Duration) -> Self {
+        d.as_secs_f64().into()
+    }
+}
+
 impl From<bool> for Value {
     fn from(b: bool) -> Self {
         Value::Boolean(Formatted::new(b))
@@ -349,9 +838,156 @@ impl<K: Into<Key>, V: Into<Value>> FromIterator<(K, V)> for Value {
     }
 }
 
+/// Compares the logical values, ignoring formatting and decor
+///
+/// Floats are compared numerically, except that `nan` is equal to itself so that `Value` can be
+/// `Eq`.  Inline tables are compared without regard to key order.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::String(lhs), Value::String(rhs)) => lhs.value() == rhs.value(),
+            (Value::Integer(lhs), Value::Integer(rhs)) => lhs.value() == rhs.value(),
+            (Value::Float(lhs), Value::Float(rhs)) => {
+                lhs.value() == rhs.value() || (lhs.value().is_nan() && rhs.value().is_nan())
+            }
+            (Value::Boolean(lhs), Value::Boolean(rhs)) => lhs.value() == rhs.value(),
+            (Value::Datetime(lhs), Value::Datetime(rhs)) => lhs.value() == rhs.value(),
+            (Value::Array(lhs), Value::Array(rhs)) => {
+                lhs.len() == rhs.len() && lhs.iter().zip(rhs.iter()).all(|(l, r)| l == r)
+            }
+            (Value::InlineTable(lhs), Value::InlineTable(rhs)) => {
+                lhs.len() == rhs.len()
+                    && lhs
+                        .iter()
+                        .all(|(key, l)| rhs.get(key).map(|r| l == r).unwrap_or(false))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialEq<i64> for Value {
+    #[inline]
+    fn eq(&self, other: &i64) -> bool {
+        self.as_integer() == Some(*other)
+    }
+}
+
+/// Like comparing two [`Value`]s, `nan` equals `nan`
+impl PartialEq<f64> for Value {
+    #[inline]
+    fn eq(&self, other: &f64) -> bool {
+        self.as_float()
+            .map_or(false, |f| f == *other || (f.is_nan() && other.is_nan()))
+    }
+}
+
+impl PartialEq<bool> for Value {
+    #[inline]
+    fn eq(&self, other: &bool) -> bool {
+        self.as_bool() == Some(*other)
+    }
+}
+
+impl PartialEq<str> for Value {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for Value {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl PartialEq<String> for Value {
+    #[inline]
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == Some(other.as_str())
+    }
+}
+
+/// Hashes the logical value, consistent with `PartialEq`
+///
+/// Floats hash their bit pattern, with `-0.0` hashed as `0.0` and every `nan` hashed alike.
+impl core::hash::Hash for Value {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Value::String(v) => v.value().hash(state),
+            Value::Integer(v) => v.value().hash(state),
+            Value::Float(v) => {
+                let f = *v.value();
+                let f = if f.is_nan() {
+                    f64::NAN
+                } else if f == 0.0 {
+                    0.0
+                } else {
+                    f
+                };
+                f.to_bits().hash(state);
+            }
+            Value::Boolean(v) => v.value().hash(state),
+            Value::Datetime(v) => v.value().hash(state),
+            Value::Array(a) => {
+                a.len().hash(state);
+                for v in a.iter() {
+                    v.hash(state);
+                }
+            }
+            Value::InlineTable(t) => {
+                let mut entries = t.iter().collect::<Vec<_>>();
+                entries.sort_by_key(|(key, _)| *key);
+                entries.len().hash(state);
+                for (key, v) in entries {
+                    key.hash(state);
+                    v.hash(state);
+                }
+            }
+        }
+    }
+}
+
+/// Resets the formatting of `value` and everything within it to a single line
 #[cfg(feature = "display")]
-impl std::fmt::Display for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+fn compact(value: &mut Value) {
+    match value {
+        Value::String(f) if f.value().contains('\n') => {
+            let repr = crate::encode::to_string_repr(
+                f.value(),
+                Some(crate::encode::StringStyle::OnelineSingle),
+                Some(false),
+            );
+            f.set_repr_unchecked(repr);
+        }
+        Value::String(f) => f.fmt(),
+        Value::Integer(f) => f.fmt(),
+        Value::Float(f) => f.fmt(),
+        Value::Boolean(f) => f.fmt(),
+        Value::Datetime(f) => f.fmt(),
+        Value::Array(array) => {
+            array.iter_mut().for_each(compact);
+            array.fmt();
+        }
+        Value::InlineTable(table) => {
+            for (mut key, value) in table.iter_mut() {
+                key.fmt();
+                compact(value);
+            }
+            table.fmt();
+            table.set_preamble("");
+        }
+    }
+}
+
+#[cfg(feature = "display")]
+impl core::fmt::Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         crate::encode::Encode::encode(self, f, None, ("", ""))
     }
 }
@@ -375,4 +1011,142 @@ mod tests {
         let features: Value = features.iter().cloned().collect();
         assert_eq!(features.to_string(), r#"["node", "mouth"]"#);
     }
+
+    #[test]
+    fn lengths() {
+        let s = Value::from("a\u{e9}\u{1f980}");
+        assert_eq!(s.char_len(), Some(3));
+        assert_eq!(s.len(), None);
+
+        let array = Value::from_iter([1, 2]);
+        assert_eq!(array.len(), Some(2));
+        assert!(!array.is_empty());
+        assert_eq!(array.char_len(), None);
+        assert!(Value::InlineTable(InlineTable::new()).is_empty());
+
+        let int = Value::from(123);
+        assert_eq!(int.char_len(), None);
+        assert_eq!(int.len(), None);
+        assert!(!int.is_empty());
+    }
+
+    #[test]
+    fn clone_bare() {
+        let value = Value::from(1).decorated(" ", " # comment");
+        assert_eq!(
+            value.decor().suffix().and_then(|s| s.as_str()),
+            Some(" # comment")
+        );
+        let bare = value.clone_bare();
+        assert_eq!(bare.decor().suffix(), None);
+        assert_eq!(bare.to_string(), "1");
+    }
+
+    #[test]
+    fn typed_constructors() {
+        assert_eq!(Value::integer(3).to_string(), "3");
+        assert_eq!(Value::float(3.0).to_string(), "3.0");
+        assert_eq!(Value::boolean(true).to_string(), "true");
+        assert_eq!(Value::string("3").to_string(), r#""3""#);
+        let date = Date {
+            year: 1979,
+            month: 5,
+            day: 27,
+        };
+        assert_eq!(Value::datetime(date).to_string(), "1979-05-27");
+    }
+
+    #[test]
+    fn hash_ignores_decor() {
+        let mut set = std::collections::HashSet::new();
+        set.insert("1".parse::<Value>().unwrap());
+        set.insert(Value::from(1).decorated(" ", " # one"));
+        set.insert("0x1".parse::<Value>().unwrap());
+        assert_eq!(set.len(), 1);
+
+        set.insert("{ a = 1, b = [1, 2] }".parse::<Value>().unwrap());
+        set.insert("{b=[ 1,2 ],a=1}".parse::<Value>().unwrap());
+        assert_eq!(set.len(), 2);
+
+        set.insert("nan".parse::<Value>().unwrap());
+        set.insert("-nan".parse::<Value>().unwrap());
+        set.insert("0.0".parse::<Value>().unwrap());
+        set.insert("-0.0".parse::<Value>().unwrap());
+        assert_eq!(set.len(), 4);
+
+        assert_ne!(Value::from(1), Value::from(1.0));
+        assert_ne!(Value::from(1), Value::from("1"));
+    }
+
+    #[test]
+    fn into_array_and_inline_table() {
+        let value = "[1, 2]".parse::<Value>().unwrap();
+        let array = value.into_array().unwrap();
+        assert_eq!(array.len(), 2);
+
+        let value = "{ a = 1 }".parse::<Value>().unwrap();
+        let value = value.into_array().unwrap_err();
+        let table = value.into_inline_table().unwrap();
+        assert_eq!(table.to_string(), "{ a = 1 }");
+
+        let value = Value::from(42);
+        let value = value.into_inline_table().unwrap_err();
+        assert_eq!(value.as_integer(), Some(42));
+    }
+
+    #[test]
+    fn duration_round_trip() {
+        let value = Value::from(Duration::from_millis(1500));
+        assert_eq!(value.to_string(), "1.5");
+        let value = value.to_string().parse::<Value>().unwrap();
+        assert_eq!(value.as_duration(), Some(Duration::from_millis(1500)));
+
+        assert_eq!(Value::from(30).as_duration(), Some(Duration::from_secs(30)));
+        assert_eq!(Value::from(-1).as_duration(), None);
+        assert_eq!(Value::from(f64::NAN).as_duration(), None);
+        assert_eq!(Value::from("1s").as_duration(), None);
+    }
+
+    #[test]
+    fn coerce_to() {
+        let port = "\"8080\"".parse::<Value>().unwrap().decorated(" ", " ");
+        let port = port.coerce_to(ValueKind::Integer).unwrap();
+        assert_eq!(port.to_string(), " 8080 ");
+
+        let err = Value::from(1.5).coerce_to(ValueKind::Integer).unwrap_err();
+        assert_eq!(err.to_string(), "cannot coerce float to integer");
+        assert_eq!(
+            Value::from(2.0).coerce_to(ValueKind::Integer),
+            Ok(Value::from(2))
+        );
+        assert!(Value::from(1e19).coerce_to(ValueKind::Integer).is_err());
+
+        assert_eq!(
+            Value::from(3).coerce_to(ValueKind::Float),
+            Ok(Value::from(3.0))
+        );
+        assert!(Value::from(i64::MAX).coerce_to(ValueKind::Float).is_err());
+        assert!(Value::from(1).coerce_to(ValueKind::Boolean).is_err());
+        assert_eq!(
+            Value::from(1).coerce_to_lenient(ValueKind::Boolean),
+            Ok(Value::from(true))
+        );
+        assert_eq!(
+            Value::from(0).coerce_to_lenient(ValueKind::Boolean),
+            Ok(Value::from(false))
+        );
+        assert!(Value::from(2)
+            .coerce_to_lenient(ValueKind::Boolean)
+            .is_err());
+        assert!(Value::from("yes").coerce_to(ValueKind::Boolean).is_err());
+        assert!(Value::from(" 1").coerce_to(ValueKind::Integer).is_err());
+        assert!(Value::from("nan").coerce_to(ValueKind::Float).is_err());
+        assert_eq!(
+            Value::from(true).coerce_to(ValueKind::String),
+            Ok(Value::from("true"))
+        );
+        assert!(Value::from(Array::new())
+            .coerce_to(ValueKind::String)
+            .is_err());
+    }
 }
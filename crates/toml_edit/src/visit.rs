@@ -74,6 +74,7 @@
 //! For a more complex example where the visitor has internal state, see `examples/visit.rs`
 //! [on GitHub](https://github.com/toml-rs/toml/blob/main/crates/toml_edit/examples/visit.rs).
 
+use crate::prelude::*;
 use crate::{
     Array, ArrayOfTables, Datetime, Document, Formatted, InlineTable, Item, Table, TableLike, Value,
 };
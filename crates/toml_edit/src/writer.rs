@@ -0,0 +1,146 @@
+use alloc::collections::BTreeSet;
+use core::fmt::Write;
+
+use crate::prelude::*;
+use crate::{Key, Value};
+
+/// Writes a TOML document one key/value pair at a time
+///
+/// Unlike building a [`Document`][crate::Document] and then rendering it, only the paths written
+/// so far are kept in memory.  A table's header is written lazily, when its first key/value pair
+/// is pushed, and the output is formatted like a rendered `Document`.
+///
+/// All pairs of a table must be pushed together, as TOML does not allow a table to be reopened.
+/// Arrays of tables are not supported.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "parse")] {
+/// use toml_edit::DocumentWriter;
+///
+/// let mut writer = DocumentWriter::new(String::new());
+/// writer.push(&["title"], "example").unwrap();
+/// writer.push(&["server", "port"], 8080).unwrap();
+/// writer.push(&["server", "host"], "localhost").unwrap();
+/// assert_eq!(
+///     writer.finish(),
+///     "title = \"example\"\n\n[server]\nport = 8080\nhost = \"localhost\"\n"
+/// );
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DocumentWriter<W> {
+    out: W,
+    current: Vec<String>,
+    closed: BTreeSet<Vec<String>>,
+    keys: BTreeSet<Vec<String>>,
+    is_empty: bool,
+}
+
+impl<W: Write> DocumentWriter<W> {
+    /// Creates a writer appending to `out`
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            current: Vec::new(),
+            closed: BTreeSet::new(),
+            keys: BTreeSet::new(),
+            is_empty: true,
+        }
+    }
+
+    /// Writes `value` under `path`, the last segment of which is its key
+    ///
+    /// The other segments name the table the pair belongs to, and its header is written if the
+    /// previous pair belonged to another table.
+    ///
+    /// # Errors
+    ///
+    /// If the table was left by an earlier push, or `path` overlaps with a key or table already
+    /// written.  Nothing is written then.
+    ///
+    /// # Panics
+    ///
+    /// If `path` is empty.
+    pub fn push<V: Into<Value>>(&mut self, path: &[&str], value: V) -> Result<(), WriteError> {
+        let (key, table) = path.split_last().expect("path must not be empty");
+        let path = path.iter().map(|s| (*s).to_owned()).collect::<Vec<_>>();
+        if self.keys.contains(&path)
+            || self
+                .closed
+                .range(path.clone()..)
+                .next()
+                .map_or(false, |closed| closed.starts_with(&path))
+        {
+            return Err(WriteError::Duplicate(path));
+        }
+        let table = &path[..table.len()];
+        if self.current != table {
+            if self.closed.contains(table) {
+                return Err(WriteError::Reopened(table.to_vec()));
+            }
+            if let Some(len) = (1..=table.len()).find(|len| self.keys.contains(&table[..*len])) {
+                return Err(WriteError::Duplicate(table[..len].to_vec()));
+            }
+            self.closed
+                .insert(core::mem::replace(&mut self.current, table.to_vec()));
+            if !self.is_empty {
+                writeln!(self.out)?;
+            }
+            write!(self.out, "[")?;
+            for (i, segment) in self.current.iter().enumerate() {
+                if i != 0 {
+                    write!(self.out, ".")?;
+                }
+                write!(self.out, "{}", Key::new(segment.as_str()).display_repr())?;
+            }
+            writeln!(self.out, "]")?;
+        }
+        let value = value.into();
+        writeln!(
+            self.out,
+            "{} = {}",
+            Key::new(*key).display_repr(),
+            value.clone_bare()
+        )?;
+        self.keys.insert(path);
+        self.is_empty = false;
+        Ok(())
+    }
+
+    /// Returns the output written to
+    pub fn finish(self) -> W {
+        self.out
+    }
+}
+
+/// A key/value pair could not be written by [`DocumentWriter::push`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WriteError {
+    /// Writing to the output failed
+    Fmt(core::fmt::Error),
+    /// The table at this path was already left, and TOML does not allow reopening it
+    Reopened(Vec<String>),
+    /// This path was already written, either as a key or as a table
+    Duplicate(Vec<String>),
+}
+
+impl From<core::fmt::Error> for WriteError {
+    fn from(err: core::fmt::Error) -> Self {
+        Self::Fmt(err)
+    }
+}
+
+impl core::fmt::Display for WriteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Fmt(err) => err.fmt(f),
+            Self::Reopened(path) => write!(f, "table `{}` was already written", path.join(".")),
+            Self::Duplicate(path) => write!(f, "key `{}` was already written", path.join(".")),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WriteError {}
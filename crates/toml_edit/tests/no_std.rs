@@ -0,0 +1,18 @@
+//! Parsing, editing and rendering without the `std` feature
+//!
+//! CI runs this with `--no-default-features --features parse,display`, so `toml_edit` is built
+//! with only `alloc`.
+
+#[test]
+fn parse_edit_render() {
+    let mut doc = "[package]\nname = \"foo\" # comment\n"
+        .parse::<toml_edit::Document>()
+        .unwrap();
+    doc["package"]["version"] = toml_edit::value("1.0");
+    assert_eq!(
+        doc.to_string(),
+        "[package]\nname = \"foo\" # comment\nversion = \"1.0\"\n"
+    );
+
+    assert!("a = ".parse::<toml_edit::Document>().is_err());
+}
@@ -254,3 +254,84 @@ value is out of range
 "#
     );
 }
+
+#[test]
+fn compare_instants() {
+    use std::cmp::Ordering;
+
+    let document = r#"
+utc = 1979-05-27T07:32:00Z
+pdt = 1979-05-27T00:32:00-07:00
+ist = 1979-05-27T13:02:00.5+05:30
+local = 1979-05-27T07:32:00
+date = 1979-05-27
+time = 07:32:00
+"#
+    .parse::<toml_edit::Document>()
+    .unwrap();
+    let get = |key: &str| *document[key].as_datetime().unwrap();
+
+    assert_ne!(get("utc"), get("pdt"));
+    assert!(get("utc").eq_instant(&get("pdt")));
+    assert_eq!(get("pdt").cmp_instant(&get("ist")), Some(Ordering::Less));
+    assert_eq!(get("ist").cmp_instant(&get("utc")), Some(Ordering::Greater));
+
+    assert_eq!(get("utc").cmp_instant(&get("local")), None);
+    assert_eq!(get("local").cmp_instant(&get("date")), None);
+    assert_eq!(get("date").cmp_instant(&get("time")), None);
+    assert_eq!(
+        get("local").cmp_instant(&get("local")),
+        Some(Ordering::Equal)
+    );
+    assert_eq!(get("date").cmp_instant(&get("date")), Some(Ordering::Equal));
+}
+
+#[test]
+fn system_time() {
+    use std::time::{Duration, UNIX_EPOCH};
+    use toml_edit::Datetime;
+
+    let leap_day = UNIX_EPOCH + Duration::from_secs(951_782_400);
+    assert_eq!(
+        Datetime::from_system_time(leap_day).to_string(),
+        "2000-02-29T00:00:00Z"
+    );
+    let before_epoch = UNIX_EPOCH - Duration::from_millis(1_500);
+    assert_eq!(
+        Datetime::from_system_time(before_epoch).to_string(),
+        "1969-12-31T23:59:58.5Z"
+    );
+
+    let now = Datetime::now_utc();
+    assert_eq!(now.offset, Some(toml_edit::Offset::Z));
+    let document = format!("stamp = {now}")
+        .parse::<toml_edit::Document>()
+        .unwrap();
+    assert_eq!(document["stamp"].as_datetime(), Some(&now));
+}
+
+#[test]
+fn to_utc() {
+    use toml_edit::Datetime;
+
+    let get = |s: &str| s.parse::<Datetime>().unwrap();
+    assert_eq!(
+        get("1979-05-27T00:32:00-07:00").to_utc(),
+        Some(get("1979-05-27T07:32:00Z"))
+    );
+    assert_eq!(
+        get("1979-05-27T20:32:00.25-07:00").to_utc(),
+        Some(get("1979-05-28T03:32:00.25Z"))
+    );
+    assert_eq!(
+        get("2000-03-01T01:00:00+05:30").to_utc(),
+        Some(get("2000-02-29T19:30:00Z"))
+    );
+    assert_eq!(
+        get("1979-05-27T07:32:00Z").to_utc(),
+        Some(get("1979-05-27T07:32:00Z"))
+    );
+    assert_eq!(get("1979-05-27T07:32:00").to_utc(), None);
+    assert_eq!(get("1979-05-27").to_utc(), None);
+    assert_eq!(get("07:32:00").to_utc(), None);
+}
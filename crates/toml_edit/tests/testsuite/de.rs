@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+
+use serde::de::IntoDeserializer as _;
+use serde::Deserialize as _;
+
+#[test]
+fn from_slice_invalid_utf8_offset() {
+    let err = toml_edit::de::from_slice::<BTreeMap<String, String>>(b"a = \"b\xffc\"").unwrap_err();
+    assert_eq!(err.utf8_offset(), Some(6));
+    assert_eq!(err.span(), Some(6..7));
+    assert_eq!(
+        err.message(),
+        "invalid utf-8 sequence of 1 bytes from index 6"
+    );
+
+    let err = toml_edit::de::from_slice::<BTreeMap<String, String>>(b"a = \"\xe2\x82").unwrap_err();
+    assert_eq!(err.utf8_offset(), Some(5));
+    assert_eq!(err.span(), Some(5..7));
+}
+
+#[test]
+fn from_slice_parse_error_has_no_utf8_offset() {
+    let err = toml_edit::de::from_slice::<BTreeMap<String, String>>(b"a = ").unwrap_err();
+    assert_eq!(err.utf8_offset(), None);
+}
+
+#[test]
+fn datetime_into_string() {
+    #[derive(Debug, serde::Deserialize)]
+    struct Release {
+        date: String,
+    }
+
+    let input = "date = 1979-05-27\n";
+    assert!(toml_edit::de::from_str::<Release>(input).is_err());
+
+    let doc = input.parse::<toml_edit::Document>().unwrap();
+    let table = doc.as_table().clone();
+    let release = Release::deserialize(table.into_deserializer()).unwrap();
+    assert_eq!(release.date, "1979-05-27");
+}
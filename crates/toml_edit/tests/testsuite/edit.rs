@@ -155,6 +155,28 @@ dc = "eqdc10"
     );
 }
 
+#[test]
+fn test_append_table() {
+    given("")
+        .running(|root| {
+            root.append_table("db").append_table("pool");
+        })
+        .produces_display(
+            r#"[db]
+
+[db.pool]
+"#,
+        );
+}
+
+#[test]
+#[should_panic]
+fn test_append_table_conflict() {
+    given("db = 1").running(|root| {
+        root.append_table("db");
+    });
+}
+
 #[test]
 fn test_insert_array() {
     given(
@@ -400,6 +422,270 @@ fn test_remove_value() {
     );
 }
 
+#[test]
+fn test_remove_preserves_order() {
+    given(
+        r#"
+a = 1
+b = 2
+c = 3
+d = 4"#,
+    )
+    .running(|root| {
+        assert_eq!(root.remove("b").and_then(|i| i.as_integer()), Some(2));
+    })
+    .produces_display(
+        r#"
+a = 1
+c = 3
+d = 4
+"#,
+    );
+}
+
+#[test]
+fn test_swap_remove() {
+    given(
+        r#"
+a = 1
+b = 2
+c = 3
+d = 4"#,
+    )
+    .running(|root| {
+        assert_eq!(root.swap_remove("b").and_then(|i| i.as_integer()), Some(2));
+        assert!(root.swap_remove("b").is_none());
+    })
+    .produces_display(
+        r#"
+a = 1
+d = 4
+c = 3
+"#,
+    );
+}
+
+#[test]
+fn test_get_mut_does_not_insert() {
+    given(
+        r#"
+a = 1
+b = 2"#,
+    )
+    .running(|root| {
+        let len = root.len();
+        assert!(root.get_mut("missing").is_none());
+        assert_eq!(root.len(), len);
+        assert!(!root.contains_key("missing"));
+
+        *root.get_mut("b").unwrap() = value(3);
+    })
+    .produces_display(
+        r#"
+a = 1
+b = 3
+"#,
+    );
+}
+
+#[test]
+fn test_table_into_iter() {
+    given(
+        r#"
+a = 1
+b = 2"#,
+    )
+    .running(|root| {
+        root["c"] = Item::None;
+        let owned = root.clone().into_iter().collect::<Vec<_>>();
+        assert_eq!(owned.len(), 2);
+        assert_eq!(owned[0].0.as_str(), "a");
+        assert_eq!(owned[1].1.as_integer(), Some(2));
+    })
+    .produces_display(
+        r#"
+a = 1
+b = 2
+"#,
+    );
+}
+
+#[test]
+fn test_typed_getters() {
+    given(
+        r#"
+a = 1
+[b]
+[[c]]"#,
+    )
+    .running(|root| {
+        assert!(root.get_value("a").is_some());
+        assert!(root.get_value("b").is_none());
+        assert!(root.get_value("missing").is_none());
+
+        assert!(root.get_table("b").is_some());
+        assert!(root.get_table("a").is_none());
+        assert!(root.get_table("c").is_none());
+
+        assert_eq!(root.get_array_of_tables("c").map(|a| a.len()), Some(1));
+        assert!(root.get_array_of_tables("b").is_none());
+
+        assert!(root.get_table_mut("a").is_none());
+        root.get_table_mut("b").unwrap()["d"] = value(2);
+    })
+    .produces_display(
+        r#"
+a = 1
+[b]
+d = 2
+[[c]]
+"#,
+    );
+}
+
+#[test]
+fn test_replace_contents() {
+    given(
+        r#"
+[package]
+name = "foo"
+
+# Generated
+[dependencies] # do not edit
+old = "1"
+"#,
+    )
+    .running(|root| {
+        let mut generated = Table::new();
+        generated["new"] = value("2");
+        let dependencies = root.get_mut("dependencies").unwrap();
+        as_table!(dependencies).replace_contents(generated);
+    })
+    .produces_display(
+        r#"
+[package]
+name = "foo"
+
+# Generated
+[dependencies] # do not edit
+new = "2"
+"#,
+    );
+}
+
+#[test]
+fn test_logical_key_lookup() {
+    given(
+        r#"
+"a.b" = 1
+'c d' = 2
+e.f = 3"#,
+    )
+    .running(|root| {
+        assert!(root.contains_key("a.b"));
+        assert_eq!(root.get("a.b").and_then(|i| i.as_integer()), Some(1));
+        assert!(root.contains_key("c d"));
+
+        assert!(!root.contains_key("e.f"));
+        assert!(root.get("e.f").is_none());
+        assert_eq!(root["e"]["f"].as_integer(), Some(3));
+
+        root.insert("g.h", value(4));
+        assert!(root.contains_key("g.h"));
+        assert!(root.remove("'c d'").is_none());
+        assert!(root.remove("c d").is_some());
+    })
+    .produces_display(
+        r#"
+"a.b" = 1
+e.f = 3
+"g.h" = 4
+"#,
+    );
+}
+
+#[test]
+fn test_decor_comment_and_blank_lines() {
+    given("a = 1")
+        .running(|root| {
+            root.insert("answer", value(42));
+            root.key_decor_mut("answer").unwrap().set_prefix(
+                toml_edit::Decor::blank_lines(2) + &toml_edit::Decor::with_comment("the answer"),
+            );
+        })
+        .produces_display(
+            r#"a = 1
+
+
+# the answer
+answer = 42
+"#,
+        );
+}
+
+#[test]
+fn test_set_str() {
+    given(
+        r#"
+name = "old" # comment
+path = 'C:\old'
+lines = """
+old"""
+count = 1"#,
+    )
+    .running(|root| {
+        assert!(root["name"].as_value_mut().unwrap().set_str("new"));
+        assert!(root["path"].as_value_mut().unwrap().set_str(r"C:\new"));
+        assert!(root["lines"].as_value_mut().unwrap().set_str("new\nlines"));
+        assert!(!root["count"].as_value_mut().unwrap().set_str("new"));
+    })
+    .produces_display(
+        r#"
+name = "new" # comment
+path = 'C:\new'
+lines = """
+new
+lines"""
+count = 1
+"#,
+    );
+}
+
+#[test]
+fn test_set_datetime() {
+    given(
+        r#"
+space = 1979-05-27 07:32:00Z # comment
+t = 1979-05-27T07:32:00z
+changed = 1979-05-27T07:32:00Z
+count = 1"#,
+    )
+    .running(|root| {
+        let dt = "2000-01-01T12:00:00Z"
+            .parse::<toml_edit::Datetime>()
+            .unwrap();
+        assert!(root["space"].as_value_mut().unwrap().set_datetime(dt));
+        assert!(root["t"].as_value_mut().unwrap().set_datetime(dt));
+        let format = toml_edit::DatetimeFormat {
+            space_separator: true,
+            lowercase_z: false,
+        };
+        assert!(root["changed"]
+            .as_value_mut()
+            .unwrap()
+            .set_datetime_with(dt, format));
+        assert!(!root["count"].as_value_mut().unwrap().set_datetime(dt));
+    })
+    .produces_display(
+        r#"
+space = 2000-01-01 12:00:00Z # comment
+t = 2000-01-01T12:00:00z
+changed = 2000-01-01 12:00:00Z
+count = 1
+"#,
+    );
+}
+
 #[test]
 fn test_remove_last_value_from_implicit() {
     given(
@@ -422,6 +708,63 @@ fn test_remove_last_value_from_implicit() {
     .produces_display(r#""#);
 }
 
+#[test]
+fn test_section_spacing() {
+    let mut doc = r#"[a]
+x = 1
+[b]
+y = 2
+
+
+
+# about c
+
+[c]
+z = 3
+[[d]]
+[[d]]
+"#
+    .parse::<Document>()
+    .unwrap();
+    doc.set_section_spacing(1);
+    assert_eq(
+        r#"[a]
+x = 1
+
+[b]
+y = 2
+
+# about c
+
+[c]
+z = 3
+
+[[d]]
+
+[[d]]
+"#,
+        doc.to_string(),
+    );
+
+    doc.insert("top", value(true));
+    doc.set_section_spacing(0);
+    assert_eq(
+        r#"top = true
+[a]
+x = 1
+[b]
+y = 2
+# about c
+
+[c]
+z = 3
+[[d]]
+[[d]]
+"#,
+        doc.to_string(),
+    );
+}
+
 // values
 
 #[test]
@@ -494,6 +837,28 @@ fn test_sort_values_by() {
     );
 }
 
+#[test]
+fn test_sort_values_keeps_leading_spacing() {
+    given(
+        r#"b = 2
+
+# this comment is attached to a
+a = 1
+c = 3"#,
+    )
+    .running(|root| {
+        root.sort_values();
+    })
+    .produces_display(
+        r#"# this comment is attached to a
+a = 1
+
+b = 2
+c = 3
+"#,
+    );
+}
+
 #[test]
 fn test_set_position() {
     given(
@@ -575,6 +940,59 @@ fn test_multiple_max_usize_positions() {
     );
 }
 
+#[test]
+fn test_walk_mut() {
+    let mut doc = r#"
+name = "hello" # greeting
+count = 1
+tags = ["a", 'b', { c = "c" }]
+
+[nested.deeper]
+key = "value"
+
+[[bin]]
+path = "src/main.rs"
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    let mut paths = Vec::new();
+    doc.as_item_mut().walk_mut(|path, value| {
+        if let Some(s) = value.as_str() {
+            paths.push(path.join("."));
+            let decor = value.decor().clone();
+            *value = Value::from(s.to_uppercase());
+            *value.decor_mut() = decor;
+        }
+    });
+
+    assert_eq!(
+        paths,
+        [
+            "name",
+            "tags.0",
+            "tags.1",
+            "tags.2.c",
+            "nested.deeper.key",
+            "bin.0.path"
+        ]
+    );
+    assert_eq(
+        r#"
+name = "HELLO" # greeting
+count = 1
+tags = ["A", "B", { c = "C" }]
+
+[nested.deeper]
+key = "VALUE"
+
+[[bin]]
+path = "SRC/MAIN.RS"
+"#,
+        doc.to_string(),
+    );
+}
+
 macro_rules! as_array {
     ($entry:ident) => {{
         assert!($entry.is_value());
@@ -633,6 +1051,678 @@ fn test_insert_replace_into_array() {
     );
 }
 
+#[test]
+fn test_typed_arrays() {
+    given("")
+        .running(|root| {
+            root["strings"] = value(toml_edit::Array::of_strings(["a", "b"]));
+            root["integers"] = value(toml_edit::Array::of_integers([1, 2]));
+            root["floats"] = value(toml_edit::Array::of_floats([1.0, 2.5]));
+        })
+        .produces_display(
+            r#"strings = ["a", "b"]
+integers = [1, 2]
+floats = [1.0, 2.5]
+"#,
+        );
+}
+
+#[test]
+fn test_array_of_inline_tables() {
+    given("")
+        .running(|root| {
+            let servers = ["a", "b"].into_iter().map(|name| {
+                let mut server = toml_edit::InlineTable::new();
+                server.insert("name", name.into());
+                server
+            });
+            root["servers"] = value(toml_edit::Array::of_inline_tables(servers));
+        })
+        .produces_display(
+            r#"servers = [{ name = "a" }, { name = "b" }]
+"#,
+        );
+}
+
+#[test]
+fn test_array_first_last() {
+    given("a = [1, 2, 3]\nb = []")
+        .running(|root| {
+            let a = root.get_mut("a").unwrap();
+            let a = as_array!(a);
+            assert_eq!(a.first().and_then(|v| v.as_integer()), Some(1));
+            assert_eq!(a.last().and_then(|v| v.as_integer()), Some(3));
+            assert_eq!(a.get(1).and_then(|v| v.as_integer()), Some(2));
+            *a.first_mut().unwrap() = 0.into();
+            *a.last_mut().unwrap() = 4.into();
+
+            let b = root.get_mut("b").unwrap();
+            let b = as_array!(b);
+            assert!(b.first().is_none());
+            assert!(b.last_mut().is_none());
+        })
+        .produces_display("a = [0, 2, 4]\nb = []\n");
+}
+
+#[test]
+fn test_array_truncate_resize() {
+    given(
+        r#"a = [1,2,3,4]
+b = [1]
+c = [
+  1,
+  2,
+  3
+]
+d = [1, 2]
+e = [
+  1,
+  2
+]
+f = [
+  1,
+  2,
+]
+"#,
+    )
+    .running(|root| {
+        let a = root.get_mut("a").unwrap();
+        let a = as_array!(a);
+        a.truncate(2);
+        a.truncate(5);
+
+        let b = root.get_mut("b").unwrap();
+        let b = as_array!(b);
+        b.resize_with(3, || 0.into());
+
+        let c = root.get_mut("c").unwrap();
+        let c = as_array!(c);
+        c.truncate(2);
+
+        let d = root.get_mut("d").unwrap();
+        let d = as_array!(d);
+        d.resize_with(0, || unreachable!());
+
+        let e = root.get_mut("e").unwrap();
+        let e = as_array!(e);
+        e.truncate(0);
+
+        let f = root.get_mut("f").unwrap();
+        let f = as_array!(f);
+        f.truncate(0);
+    })
+    .produces_display(
+        r#"a = [1,2]
+b = [1, 0, 0]
+c = [
+  1,
+  2
+]
+d = []
+e = [
+]
+f = [
+]
+"#,
+    );
+}
+
+#[test]
+fn test_set_indent() {
+    let mut doc = r#"top = 1
+[section]
+a = 1
+# about b
+b.c = 2
+
+[[items]]
+name = "x"
+"#
+    .parse::<Document>()
+    .unwrap();
+    doc.set_indent(2);
+    assert_eq!(
+        doc.to_string(),
+        r#"top = 1
+[section]
+  a = 1
+  # about b
+  b.c = 2
+
+[[items]]
+  name = "x"
+"#
+    );
+}
+
+#[test]
+fn test_align_assignments() {
+    let mut doc = r#"a = 1
+[package]
+name = "foo"
+version = "1.0"
+
+# grouped separately
+edition = "2021"
+x.y = 1
+rust-version = "1.67"
+
+[[bin]]
+id = 1
+path = "main.rs"
+"#
+    .parse::<Document>()
+    .unwrap();
+    doc.align_assignments(true);
+    assert_eq!(
+        doc.to_string(),
+        r#"a = 1
+[package]
+name    = "foo"
+version = "1.0"
+
+# grouped separately
+edition = "2021"
+x.y = 1
+rust-version = "1.67"
+
+[[bin]]
+id   = 1
+path = "main.rs"
+"#
+    );
+
+    doc.align_assignments(false);
+    assert_eq!(
+        doc.to_string(),
+        r#"a = 1
+[package]
+name = "foo"
+version = "1.0"
+
+# grouped separately
+edition = "2021"
+x.y = 1
+rust-version = "1.67"
+
+[[bin]]
+id = 1
+path = "main.rs"
+"#
+    );
+}
+
+#[test]
+fn test_table_prepend() {
+    given(
+        r#"[package]
+
+version = "1.0"
+edition = "2021"
+"#,
+    )
+    .running(|root| {
+        let package = root.get_mut("package").unwrap();
+        let package = as_table!(package);
+        assert!(package.prepend("name", value("foo")).is_none());
+        let keys = package.iter().map(|(k, _)| k).collect::<Vec<_>>();
+        assert_eq!(keys, ["name", "version", "edition"]);
+    })
+    .produces_display(
+        r#"[package]
+
+name = "foo"
+version = "1.0"
+edition = "2021"
+"#,
+    );
+}
+
+#[test]
+fn test_table_swap() {
+    given(
+        r#"[package]
+
+name = "foo" # the name
+version = "1.0"
+edition = "2021"
+"#,
+    )
+    .running(|root| {
+        let package = root.get_mut("package").unwrap();
+        let package = as_table!(package);
+        assert!(package.swap("name", "edition"));
+        assert!(!package.swap("name", "missing"));
+    })
+    .produces_display(
+        r#"[package]
+
+edition = "2021"
+version = "1.0"
+name = "foo" # the name
+"#,
+    );
+}
+
+#[test]
+fn test_table_with() {
+    given("")
+        .running(|root| {
+            let ports = toml_edit::Array::new().with(80).with(443);
+            let server = Table::new()
+                .with("host", value("localhost"))
+                .with("ports", value(ports));
+            root.insert("server", Item::Table(server));
+        })
+        .produces_display(
+            r#"[server]
+host = "localhost"
+ports = [80, 443]
+"#,
+        );
+}
+
+#[test]
+fn test_entry_and_modify() {
+    given("hits = 1")
+        .running(|root| {
+            let increment = |item: &mut Item| {
+                let hits = item.as_integer().unwrap();
+                *item = value(hits + 1);
+            };
+            root.entry("hits").and_modify(increment).or_insert(value(1));
+            root.entry("misses")
+                .and_modify(increment)
+                .or_insert(value(1));
+
+            let inline = root.entry("inline").or_insert(toml_edit::table());
+            inline.make_value();
+            let inline = inline.as_inline_table_mut().unwrap();
+            inline
+                .entry("count")
+                .and_modify(|v| *v = 0.into())
+                .or_insert(1.into());
+        })
+        .produces_display(
+            r#"hits = 2
+misses = 1
+inline = { count = 1 }
+"#,
+        );
+}
+
+const MERGE_BASE: &str = r#"name = "base"
+ports = [80]
+[server]
+host = "localhost"
+tags = ["a"]
+[[plugin]]
+id = 1
+"#;
+
+const MERGE_LAYER: &str = r#"name = "layer"
+ports = [443]
+extra = true
+[server]
+tags = ["b"]
+[[plugin]]
+id = 2
+"#;
+
+fn merged(strategy: toml_edit::MergeStrategy) -> Document {
+    let mut base = MERGE_BASE.parse::<Document>().unwrap();
+    let layer = MERGE_LAYER.parse::<Document>().unwrap();
+    base.merge_with(&layer, strategy);
+    base
+}
+
+#[test]
+fn test_merge_with_override() {
+    let doc = merged(toml_edit::MergeStrategy::Override);
+    assert_eq!(doc["name"].as_str(), Some("layer"));
+    assert_eq!(doc["ports"].to_string(), " [443]");
+    assert_eq!(doc["extra"].as_bool(), Some(true));
+    assert_eq!(doc["server"]["host"].as_str(), Some("localhost"));
+    assert_eq!(doc["server"]["tags"].to_string(), r#" ["b"]"#);
+    assert_eq!(doc["plugin"].as_array_of_tables().unwrap().len(), 1);
+    assert_eq!(doc["plugin"][0]["id"].as_integer(), Some(2));
+}
+
+#[test]
+fn test_merge_with_keep_existing() {
+    let doc = merged(toml_edit::MergeStrategy::KeepExisting);
+    assert_eq!(doc["name"].as_str(), Some("base"));
+    assert_eq!(doc["ports"].to_string(), " [80]");
+    assert_eq!(doc["extra"].as_bool(), Some(true));
+    assert_eq!(doc["server"]["tags"].to_string(), r#" ["a"]"#);
+    assert_eq!(doc["plugin"].as_array_of_tables().unwrap().len(), 1);
+    assert_eq!(doc["plugin"][0]["id"].as_integer(), Some(1));
+}
+
+#[test]
+fn test_merge_with_append_arrays() {
+    let doc = merged(toml_edit::MergeStrategy::AppendArrays);
+    assert_eq!(doc["name"].as_str(), Some("layer"));
+    assert_eq!(doc["ports"].to_string(), " [80, 443]");
+    assert_eq!(doc["server"]["host"].as_str(), Some("localhost"));
+    assert_eq!(doc["server"]["tags"].to_string(), r#" ["a", "b"]"#);
+    let ids = doc["plugin"]
+        .as_array_of_tables()
+        .unwrap()
+        .iter()
+        .map(|t| t["id"].as_integer().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, [1, 2]);
+}
+
+#[test]
+fn test_merge_with_into_inline_table() {
+    let mut base = r#"t = { a = 1, b = 1 }
+"#
+    .parse::<Document>()
+    .unwrap();
+    let layer = r#"[t]
+b = 2
+[t.sub]
+x = 1
+[[t.list]]
+y = 1
+[t.a]
+z = 1
+"#
+    .parse::<Document>()
+    .unwrap();
+    base.merge_with(&layer, toml_edit::MergeStrategy::Override);
+    assert!(base["t"].is_inline_table());
+    assert_eq!(base["t"]["a"]["z"].as_integer(), Some(1));
+    assert_eq!(base["t"]["b"].as_integer(), Some(2));
+    assert_eq!(base["t"]["sub"]["x"].as_integer(), Some(1));
+    assert_eq!(base["t"]["list"][0]["y"].as_integer(), Some(1));
+    assert!(base["t"]["a"].is_inline_table());
+    assert!(base["t"]["list"].is_array());
+}
+
+#[test]
+fn test_array_insert_formatted_and_fix_separators() {
+    given(
+        r#"a = [
+  1,
+  2,
+]
+b = [1,2]
+"#,
+    )
+    .running(|root| {
+        let a = root.get_mut("a").unwrap();
+        let a = as_array!(a);
+        let three = toml_edit::Value::from(3).decorated("\n  ", "    # three\n");
+        a.insert_formatted(2, three);
+        a.set_trailing_comma(false);
+        a.set_trailing("");
+        a.fix_separators();
+
+        let b = root.get_mut("b").unwrap();
+        let b = as_array!(b);
+        b.insert_formatted(0, toml_edit::Value::from(0).decorated("  ", "  "));
+        b.fix_separators();
+    })
+    .produces_display(
+        r#"a = [
+  1,
+  2,
+  3    # three
+]
+b = [0, 1, 2]
+"#,
+    );
+}
+
+#[test]
+fn test_array_enforce_homogeneous() {
+    given(r#"ports = [80, 443]"#)
+        .running(|root| {
+            let ports = root.get_mut("ports").unwrap();
+            let ports = as_array!(ports);
+            assert!(!ports.enforce_homogeneous());
+            ports.set_enforce_homogeneous(true);
+
+            let rejected = ports.try_push("8080").unwrap_err();
+            assert_eq!(rejected.as_str(), Some("8080"));
+            assert!(ports.try_insert(0, 1.5).is_err());
+            ports.try_push(8080).unwrap();
+            // Only the `try_` methods check
+            ports.push("mixed");
+            ports.set_enforce_homogeneous(false);
+            ports.try_insert(1, 1.5).unwrap();
+        })
+        .produces_display(
+            r#"ports = [80, 1.5, 443, 8080, "mixed"]
+"#,
+        );
+}
+
+#[test]
+fn test_table_inline_if() {
+    given(
+        r#"[a]
+x = 1
+y = 2
+[b]
+x = 1
+[b.c]
+z = 1
+"#,
+    )
+    .running(|root| {
+        assert!(root["b"].as_table().unwrap().inline_if(2, 80).is_none());
+
+        let a = root["a"].as_table().unwrap().inline_if(2, 80).unwrap();
+        root.insert("a", Item::Value(Value::InlineTable(a)));
+    })
+    .produces_display(
+        r#"a = { x = 1, y = 2 }
+[b]
+x = 1
+[b.c]
+z = 1
+"#,
+    );
+}
+
+#[test]
+fn test_array_push_with_comment() {
+    given("")
+        .running(|root| {
+            let mut numbers = toml_edit::Array::new();
+            numbers.push_formatted(Value::from(1).with_comment("one"));
+            numbers.push_formatted(Value::from(2).with_comment("two"));
+            assert_eq!(numbers.to_string(), "[1, # one\n 2 # two\n]");
+            root["numbers"] = value(numbers);
+            root["answer"] = value(Value::from(42).with_comment("no comma here"));
+        })
+        .produces_display(
+            r#"numbers = [1, # one
+ 2 # two
+]
+answer = 42 # no comma here
+"#,
+        );
+}
+
+#[test]
+fn test_string_quote_style() {
+    given(
+        r#"parsed = "C:\\temp"
+"#,
+    )
+    .running(|root| {
+        root["path"] = value(r"C:\temp");
+        root["quoted"] = value(r#"say "hi""#);
+        root["both"] = value(r#"it's "fine""#);
+    })
+    .produces_display(
+        r#"parsed = "C:\\temp"
+path = 'C:\temp'
+quoted = "say \"hi\""
+both = "it's \"fine\""
+"#,
+    );
+
+    let mut doc = "parsed = \"C:\\\\temp\"\nplain = 'x'\nquoted = \"say \\\"hi\\\"\"\n"
+        .parse::<Document>()
+        .unwrap();
+    doc.set_string_quote_style(toml_edit::StringQuoteStyle::Minimal);
+    assert_eq!(
+        doc.to_string(),
+        "parsed = 'C:\\temp'\nplain = \"x\"\nquoted = 'say \"hi\"'\n"
+    );
+    doc.set_string_quote_style(toml_edit::StringQuoteStyle::AlwaysBasic);
+    assert_eq!(
+        doc.to_string(),
+        "parsed = \"C:\\\\temp\"\nplain = \"x\"\nquoted = \"say \\\"hi\\\"\"\n"
+    );
+}
+
+#[test]
+fn test_item_merge() {
+    let other = r#"[server]
+port = 9090
+tags = ["b"]
+[[server.route]]
+path = "/b"
+"#
+    .parse::<Document>()
+    .unwrap();
+    given(
+        r#"[server]
+host = "localhost"
+port = 8080
+tags = ["a"]
+[[server.route]]
+path = "/a"
+"#,
+    )
+    .running(|root| {
+        root.get_mut("server").unwrap().merge(&other["server"]);
+    })
+    .produces_display(
+        r#"[server]
+host = "localhost"
+port = 9090
+tags = ["b"]
+[[server.route]]
+path = "/a"
+[[server.route]]
+path = "/b"
+"#,
+    );
+}
+
+#[test]
+fn test_array_dedup() {
+    given(r#"a = ["a", 'b', 'a', "b", "c"]"#)
+        .running(|root| {
+            let a = root.get_mut("a").unwrap();
+            let a = as_array!(a);
+            a.dedup();
+        })
+        .produces_display(
+            r#"a = ["a", 'b', "c"]
+"#,
+        );
+}
+
+#[test]
+fn test_item_from_collections() {
+    given("")
+        .running(|root| {
+            let mut map = std::collections::BTreeMap::new();
+            map.insert("b".to_owned(), 2);
+            map.insert("a".to_owned(), 1);
+            root["list"] = Item::from(vec!["x", "y"]);
+            root["map"] = map.into();
+        })
+        .produces_display(
+            r#"list = ["x", "y"]
+
+[map]
+a = 1
+b = 2
+"#,
+        );
+}
+
+#[test]
+fn test_or_insert_array() {
+    given("b = [0]")
+        .running(|root| {
+            let a = root["a"].or_insert_array();
+            a.push(1);
+            a.push(2);
+            root["b"].or_insert_array().push(1);
+        })
+        .produces_display("b = [0, 1]\na = [1, 2]\n");
+}
+
+#[test]
+#[should_panic = "expected an array, found integer"]
+fn test_or_insert_array_conflict() {
+    given("a = 1").running(|root| {
+        root["a"].or_insert_array();
+    });
+}
+
+#[test]
+fn test_append_array() {
+    given(
+        r#"
+        a = [1, 2]
+        b = [3, 4]
+        c = []"#,
+    )
+    .running(|root| {
+        let mut b = root.remove("b").unwrap().into_value().unwrap();
+        let b = b.as_array_mut().unwrap();
+        let mut c = b.clone();
+
+        let a = root.get_mut("a").unwrap();
+        let a = as_array!(a);
+        a.append(b);
+        assert!(b.is_empty());
+
+        let empty = root.get_mut("c").unwrap();
+        let empty = as_array!(empty);
+        empty.append(&mut c);
+    })
+    .produces_display(
+        r#"
+        a = [1, 2, 3, 4]
+        c = [3, 4]
+"#,
+    );
+}
+
+#[test]
+fn test_map_array() {
+    given(
+        r#"
+        a = [ 1,2,  3 ] # comment"#,
+    )
+    .running(|root| {
+        let a = root.get_mut("a").unwrap();
+        let a = as_array!(a);
+        let doubled = a.map(|v| Value::from(v.as_integer().unwrap() * 2));
+        assert_eq!(a.len(), 3);
+        root.insert("b", value(doubled));
+    })
+    .produces_display(
+        r#"
+        a = [ 1,2,  3 ] # comment
+b = [2, 4, 6]
+"#,
+    );
+}
+
 #[test]
 fn test_remove_from_array() {
     given(
@@ -687,6 +1777,41 @@ fn test_format_array() {
     );
 }
 
+#[test]
+fn test_push_nested_array() {
+    given("")
+        .running(|root| {
+            let mut a = toml_edit::Array::new();
+            a.push(toml_edit::Array::from_iter([1, 2]));
+            a.push(toml_edit::Array::from_iter([3, 4]));
+            root["a"] = value(a);
+        })
+        .produces_display(
+            r#"a = [[1, 2], [3, 4]]
+"#,
+        );
+}
+
+#[test]
+fn test_push_inline_table() {
+    given("")
+        .running(|root| {
+            let mut first = toml_edit::InlineTable::new();
+            first.insert("a", 1.into());
+            let mut second = toml_edit::InlineTable::new();
+            second.insert("b", 2.into());
+
+            let mut a = toml_edit::Array::new();
+            a.push(first);
+            a.push(second);
+            root["a"] = value(a);
+        })
+        .produces_display(
+            r#"a = [{ a = 1 }, { b = 2 }]
+"#,
+        );
+}
+
 macro_rules! as_inline_table {
     ($entry:ident) => {{
         assert!($entry.is_value());
@@ -757,6 +1882,27 @@ fn test_remove_from_inline_table() {
     );
 }
 
+#[test]
+fn test_inline_table_set_compact() {
+    given(
+        r#"
+a = { a = 1 }
+b = {c=2, d.e=3}"#,
+    )
+    .running(|root| {
+        let a = root.get_mut("a").unwrap();
+        as_inline_table!(a).set_compact(true);
+        let b = root.get_mut("b").unwrap();
+        as_inline_table!(b).set_compact(false);
+    })
+    .produces_display(
+        r#"
+a = {a = 1}
+b = { c=2, d.e=3 }
+"#,
+    );
+}
+
 #[test]
 fn test_as_table_like() {
     given(
@@ -854,9 +2000,109 @@ src.git = "https://github.com/nixos/nixpkgs"
         );
 }
 
+#[test]
+fn test_insert_dotted() {
+    given(
+        r#"[package]
+name = "hello"
+"#,
+    )
+    .running(|root| {
+        let package = root["package"].as_table_mut().unwrap();
+        package.insert_dotted(&Key::path(&["a", "b", "c"]), value(1));
+        package.insert_dotted(&Key::path(&["a", "b", "d"]), value(2));
+        package.insert_dotted(&Key::path(&["a", "e.f"]), value(3));
+
+        let a = package["a"].as_table().unwrap();
+        assert!(a.is_dotted());
+        assert!(a["b"].as_table().unwrap().is_dotted());
+    })
+    .produces_display(
+        r#"[package]
+name = "hello"
+a.b.c = 1
+a.b.d = 2
+a."e.f" = 3
+"#,
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_insert_dotted_into_value() {
+    given("a = 1").running(|root| {
+        root.insert_dotted(&Key::path(&["a", "b"]), value(2));
+    });
+}
+
+#[test]
+fn test_insert_non_ascii_keys() {
+    given(r#""naïve" = 0"#)
+        .running(|root| {
+            root.insert("café", value(1));
+            root.insert("🦀", value("crab"));
+        })
+        .produces_display(
+            r#""naïve" = 0
+"café" = 1
+"🦀" = "crab"
+"#,
+        );
+
+    let doc = "\"café\" = 1\n'🦀' = 2".parse::<Document>().unwrap();
+    assert_eq!(doc["café"].as_integer(), Some(1));
+    assert_eq!(doc["🦀"].as_integer(), Some(2));
+    assert_eq("\"café\" = 1\n'🦀' = 2\n", doc.to_string());
+
+    assert!("café = 1".parse::<Document>().is_err());
+}
+
 #[test]
 fn sorting_with_references() {
     let values = vec!["foo", "qux", "bar"];
     let mut array = toml_edit::Array::from_iter(values);
     array.sort_by(|lhs, rhs| lhs.as_str().cmp(&rhs.as_str()));
 }
+
+#[test]
+fn test_table_order_under_random_edits() {
+    const KEYS: [&str; 8] = ["h", "c", "a", "f", "b", "g", "d", "e"];
+
+    // xorshift, so failures are reproducible without a property-testing dependency
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for _ in 0..50 {
+        let mut table = Table::new();
+        let mut expected: Vec<&str> = Vec::new();
+        for _ in 0..40 {
+            let key = KEYS[(next() % KEYS.len() as u64) as usize];
+            match next() % 6 {
+                0..=2 => {
+                    table.insert(key, value(key));
+                    if !expected.contains(&key) {
+                        expected.push(key);
+                    }
+                }
+                3 | 4 => {
+                    table.remove(key);
+                    expected.retain(|k| *k != key);
+                }
+                _ => {
+                    if next() % 2 == 0 {
+                        table.sort_values();
+                        expected.sort_unstable();
+                    } else {
+                        table = table.clone();
+                    }
+                }
+            }
+            table.assert_order(&expected);
+        }
+    }
+}
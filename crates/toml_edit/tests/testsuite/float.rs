@@ -58,3 +58,32 @@ fn test_float() {
     float_inf_tests!(f32);
     float_inf_tests!(f64);
 }
+
+#[test]
+fn test_special_float_round_trip() {
+    let cases = [
+        ("inf", f64::INFINITY),
+        ("+inf", f64::INFINITY),
+        ("-inf", f64::NEG_INFINITY),
+        ("nan", f64::NAN.copysign(1.0)),
+        ("+nan", f64::NAN.copysign(1.0)),
+        ("-nan", f64::NAN.copysign(-1.0)),
+    ];
+    for (literal, expected) in cases {
+        let input = format!("x = {literal}\narr = [{literal}, {{ y = {literal} }}]\n");
+        let document = input.parse::<Document>().unwrap();
+        assert_eq!(document.to_string(), input);
+
+        let actual = document["x"].as_float().unwrap();
+        if expected.is_nan() {
+            assert!(actual.is_nan(), "{literal}");
+        } else {
+            assert_eq!(actual, expected, "{literal}");
+        }
+        assert_eq!(
+            actual.is_sign_negative(),
+            expected.is_sign_negative(),
+            "{literal}"
+        );
+    }
+}
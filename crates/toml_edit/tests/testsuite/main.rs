@@ -2,8 +2,11 @@
 
 mod convert;
 mod datetime;
+#[cfg(feature = "serde")]
+mod de;
 mod edit;
 mod float;
 mod invalid;
 mod parse;
 mod stackoverflow;
+mod writer;
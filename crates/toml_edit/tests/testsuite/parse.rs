@@ -1,5 +1,5 @@
 use snapbox::assert_eq;
-use toml_edit::{Document, Key, Value};
+use toml_edit::{Document, Item, Key, Value};
 
 macro_rules! parse {
     ($s:expr, $ty:ty) => {{
@@ -38,6 +38,22 @@ fn test_key_from_str() {
     );
 }
 
+#[test]
+fn test_key_parse_segments() {
+    let segments = |s: &str| {
+        Key::parse(s)
+            .unwrap()
+            .iter()
+            .map(|k| k.get().to_owned())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(segments("a.b"), ["a", "b"]);
+    assert_eq!(segments("a"), ["a"]);
+    assert_eq!(segments(r#"a . "b.c" . 'd'"#), ["a", "b.c", "d"]);
+
+    assert!("a.b".parse::<Key>().is_err());
+}
+
 #[test]
 fn test_value_from_str() {
     assert!(parse_value!("1979-05-27T00:32:00.999999-07:00").is_datetime());
@@ -1254,6 +1270,32 @@ duplicate key `b` in table `a`
     );
 }
 
+#[test]
+fn bad_table_array_conflict() {
+    bad!(
+        "[a]\nx=1\n[[a]]\ny=2\n",
+        "\
+TOML parse error at line 3, column 1
+  |
+3 | [[a]]
+  | ^
+invalid table header
+duplicate key `a` in document root
+"
+    );
+    bad!(
+        "[[a]]\nx=1\n[a]\ny=2\n",
+        "\
+TOML parse error at line 3, column 1
+  |
+3 | [a]
+  | ^
+invalid table header
+duplicate key `a` in document root
+"
+    );
+}
+
 #[test]
 fn bad_table_redefine() {
     bad!(
@@ -1488,3 +1530,1137 @@ fn despan_keys() {
 
     assert_eq!(doc.to_string(), "aaaaaa = 1\nbbb = 2\n");
 }
+
+#[test]
+fn find_all_by_predicate() {
+    let doc = r#"
+small = 1
+big = 101
+
+[server]
+port = 8080
+limits = [5, 500, { burst = 1000 }]
+
+[[worker]]
+threads = 4
+
+[[worker]]
+threads = 128
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    let found = doc
+        .find_all(|_, v| v.as_integer().map_or(false, |i| i > 100))
+        .into_iter()
+        .map(|(path, v)| (path.join("."), v.as_integer().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            ("big".to_owned(), 101),
+            ("server.port".to_owned(), 8080),
+            ("server.limits.1".to_owned(), 500),
+            ("server.limits.2.burst".to_owned(), 1000),
+            ("worker.1.threads".to_owned(), 128),
+        ]
+    );
+}
+
+#[test]
+fn crlf_round_trip() {
+    let input = "# comment\r\nname = \"hello\" # trailing\r\n\r\n[table]\r\nkey = \"\"\"\r\nmulti\r\nline\"\"\"\r\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    assert_eq!(doc.line_ending(), toml_edit::LineEnding::Crlf);
+    assert_eq!(doc.to_string(), input);
+
+    doc["table"]["added"] = toml_edit::value(1);
+    assert_eq!(
+        doc.to_string(),
+        "# comment\r\nname = \"hello\" # trailing\r\n\r\n[table]\r\nkey = \"\"\"\r\nmulti\r\nline\"\"\"\r\nadded = 1\r\n"
+    );
+
+    doc.set_line_ending(toml_edit::LineEnding::Lf);
+    assert_eq!(
+        doc.to_string(),
+        "# comment\nname = \"hello\" # trailing\n\n[table]\nkey = \"\"\"\nmulti\nline\"\"\"\nadded = 1\n"
+    );
+}
+
+#[test]
+fn line_ending_detection() {
+    let doc = "a = 1\nb = 2\r\nc = 3\n".parse::<Document>().unwrap();
+    assert_eq!(doc.line_ending(), toml_edit::LineEnding::Lf);
+    assert_eq!(doc.to_string(), "a = 1\nb = 2\nc = 3\n");
+
+    let doc = "a = 1\r\nb = 2\nc = 3\r\n".parse::<Document>().unwrap();
+    assert_eq!(doc.line_ending(), toml_edit::LineEnding::Crlf);
+    assert_eq!(doc.to_string(), "a = 1\r\nb = 2\r\nc = 3\r\n");
+}
+
+#[test]
+fn inline_table_to_map() {
+    let value = parse_value!("{ a = 1, b = 2 }");
+    let map = value.as_inline_table().unwrap().to_map();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"].as_integer(), Some(1));
+    assert_eq!(map["b"].as_integer(), Some(2));
+    assert_eq!(map["b"].to_string(), "2");
+}
+
+#[test]
+fn display_fragments() {
+    let array = toml_edit::Array::from_iter([1, 2, 3]);
+    assert_eq!(format!("{}", array), "[1, 2, 3]");
+
+    let doc = r#"
+inline = {a=1,  b = [ 1,2 ]}
+[table]
+key = "value" # comment
+dotted.key = true
+[table.child]
+hidden = 1
+"#
+    .parse::<Document>()
+    .unwrap();
+    assert_eq!(
+        format!("{}", doc["inline"].as_inline_table().unwrap()),
+        "{a=1,  b = [ 1,2 ]}"
+    );
+    assert_eq!(
+        format!("{}", doc["table"].as_table().unwrap()),
+        "key = \"value\" # comment\ndotted.key = true\n"
+    );
+}
+
+#[test]
+fn inline_table_get_path() {
+    let value = parse_value!("{ a = { b = { c = 1 } }, d.e = 2 }");
+    let table = value.as_inline_table().unwrap();
+    assert_eq!(
+        table
+            .get_path(&["a", "b", "c"])
+            .and_then(|v| v.as_integer()),
+        Some(1)
+    );
+    assert!(table.get_path(&["a", "b"]).unwrap().is_inline_table());
+    assert_eq!(
+        table.get_path(&["d", "e"]).and_then(|v| v.as_integer()),
+        Some(2)
+    );
+    assert!(table.get_path(&["a", "b", "c", "d"]).is_none());
+    assert!(table.get_path(&["a", "x"]).is_none());
+    assert!(table.get_path(&[]).is_none());
+}
+
+#[test]
+fn inline_table_iter() {
+    let mut value = parse_value!("{ a = 1, b = 2, c = 3 }");
+    let table = value.as_inline_table_mut().unwrap();
+    let keys = table.iter().map(|(k, _)| k).collect::<Vec<_>>();
+    assert_eq!(keys, ["a", "b", "c"]);
+
+    table.remove("b");
+    for (key, v) in table.iter_mut() {
+        *v = Value::from(format!("{}{}", key.get(), v.as_integer().unwrap()));
+    }
+    let entries = table
+        .iter()
+        .map(|(k, v)| (k, v.as_str().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(entries, [("a", "a1"), ("c", "c3")]);
+}
+
+#[test]
+fn inline_table_merge() {
+    let mut value = parse_value!("{ a = 1 }");
+    let other = parse_value!("{ a = 2, b = 3 }");
+    let table = value.as_inline_table_mut().unwrap();
+    table.merge(other.as_inline_table().unwrap());
+    assert_eq!(value.to_string(), "{ a = 2, b = 3 }");
+
+    let mut value = parse_value!("{a=1,nested={x=1},dotted.y=2}");
+    let other = parse_value!("{ nested = { z = 3 }, dotted = { y = 4 }, c = 5 }");
+    let table = value.as_inline_table_mut().unwrap();
+    table.merge(other.as_inline_table().unwrap());
+    assert_eq!(
+        value.to_string(),
+        "{a=1,nested={x=1, z = 3},dotted.y=4, c = 5}"
+    );
+}
+
+#[test]
+fn value_to_string_compact() {
+    let value = parse_value!(
+        r#"[
+    1,   # one
+    2,
+    3,
+]"#
+    );
+    assert_eq!(value.to_string_compact(), "[1, 2, 3]");
+
+    let value = parse_value!(
+        r#"{a=[ 'x' ,"""y
+z""" ],   "b" = 0x10 }"#
+    );
+    assert_eq!(
+        value.to_string_compact(),
+        r#"{ a = ["x", "y\nz"], b = 16 }"#
+    );
+}
+
+#[test]
+fn diff_documents() {
+    let old = r#"
+title = "app"
+[server]
+port = 8080
+hosts = ["a", "b"]
+"#
+    .parse::<Document>()
+    .unwrap();
+    let new = r#"
+title = "app"  # unchanged
+[server]
+port = 9090
+hosts = ["a", "b"]
+timeout = 30
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    let changes = old.diff(&new);
+    assert_eq!(changes.len(), 2);
+    match &changes[0] {
+        toml_edit::Change::Modified { path, old, new } => {
+            assert_eq!(path, &["server", "port"]);
+            assert_eq!(old.as_integer(), Some(8080));
+            assert_eq!(new.as_integer(), Some(9090));
+        }
+        change => panic!("unexpected change: {:?}", change),
+    }
+    match &changes[1] {
+        toml_edit::Change::Added { path, new } => {
+            assert_eq!(path, &["server", "timeout"]);
+            assert_eq!(new.as_integer(), Some(30));
+        }
+        change => panic!("unexpected change: {:?}", change),
+    }
+    assert!(new.diff(&new).is_empty());
+}
+
+#[test]
+fn key_quote_style() {
+    let mut doc = r#"
+"a" = 1
+"b c" = 2
+[d."e"]
+f = { "g" = 3 }
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    doc.set_key_quote_style(toml_edit::KeyQuoteStyle::AlwaysBare);
+    assert_eq(
+        r#"
+a = 1
+"b c" = 2
+[d.e]
+f = { g = 3 }
+"#,
+        doc.to_string(),
+    );
+
+    doc.set_key_quote_style(toml_edit::KeyQuoteStyle::AlwaysQuoted);
+    assert_eq(
+        r#"
+"a" = 1
+"b c" = 2
+["d"."e"]
+"f" = { "g" = 3 }
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn item_summary() {
+    let doc = r#"
+[package]
+name = "toml"  # the name
+version = "0.1.0"
+answer = 42
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    assert_eq!(doc["package"].summary(), "Table{3 keys}");
+    assert_eq!(doc["package"]["answer"].summary(), "Value(Integer 42)");
+    assert_eq!(doc["package"]["name"].summary(), r#"Value(String "toml")"#);
+}
+
+#[test]
+fn array_trailing_comment_round_trip() {
+    let input = r#"
+with_comma = [
+  1,
+  2,
+  # last
+]
+without_comma = [
+  1,
+  2
+  # last
+]
+"#;
+    let document = input.parse::<Document>().unwrap();
+    let with_comma = document["with_comma"].as_array().unwrap();
+    assert!(with_comma.trailing_comma());
+    assert_eq!(with_comma.trailing().as_str(), Some("\n  # last\n"));
+    assert_eq(input, document.to_string());
+}
+
+#[test]
+fn integer_as_u64() {
+    let doc = "id = 9223372036854775807\nneg = -1\n"
+        .parse::<Document>()
+        .unwrap();
+    assert_eq!(
+        doc["id"].as_value().unwrap().as_u64(),
+        Some(i64::MAX as u64)
+    );
+    assert_eq!(doc["neg"].as_value().unwrap().as_u64(), None);
+    assert_eq!(doc.to_string(), "id = 9223372036854775807\nneg = -1\n");
+
+    assert!("id = 18446744073709551615\n".parse::<Document>().is_err());
+
+    let mut options = toml_edit::ParseOptions::default();
+    options.u64_integers = true;
+    let input = "id = 18446744073709551615\nhex = 0xffff_ffff_ffff_fffe\nneg = -1\n";
+    let doc = Document::parse_with_options(input, options).unwrap();
+    assert_eq!(doc["id"].as_value().unwrap().as_u64(), Some(u64::MAX));
+    assert_eq!(doc["hex"].as_value().unwrap().as_u64(), Some(u64::MAX - 1));
+    assert_eq!(doc["neg"].as_value().unwrap().as_u64(), None);
+    assert_eq!(doc.to_string(), input);
+    assert!(Document::parse_with_options("id = 18446744073709551616\n", options).is_err());
+}
+
+#[test]
+fn tabs_round_trip() {
+    let input = "\t# comment\nname\t=\t\"toml\"\t# trailing\n[\ttable\t]\n\tkey =\t[\t1,\t2\t]\n\tinline = {\ta = 1\t}\n";
+    let document = input.parse::<Document>().unwrap();
+    assert_eq!(document["table"]["key"][1].as_integer(), Some(2));
+    assert_eq(input, document.to_string());
+}
+
+#[test]
+fn document_get() {
+    let mut doc = "title = \"TOML\"\n[owner]\nname = \"Tom\"\n"
+        .parse::<Document>()
+        .unwrap();
+    assert_eq!(doc.get("title").and_then(|i| i.as_str()), Some("TOML"));
+    assert!(doc.get("missing").is_none());
+    assert_eq!(doc["owner"]["name"].as_str(), Some("Tom"));
+
+    *doc.get_mut("title").unwrap() = toml_edit::value("TOML v1");
+    assert_eq!(
+        doc.to_string(),
+        "title = \"TOML v1\"\n[owner]\nname = \"Tom\"\n"
+    );
+}
+
+#[test]
+fn lenient_numbers() {
+    let input = "x = 007\ny = [-01.5, +002, 1979-05-07]\nz = \"007\"\n";
+    assert!(input.parse::<Document>().is_err());
+
+    let doc = Document::parse_lenient_numbers(input).unwrap();
+    assert_eq!(doc["x"].as_integer(), Some(7));
+    assert_eq!(doc["y"][0].as_float(), Some(-1.5));
+    assert_eq!(doc["y"][1].as_integer(), Some(2));
+    assert_eq!(doc["z"].as_str(), Some("007"));
+    assert_eq(
+        "x = 7\ny = [-1.5, +2, 1979-05-07]\nz = \"007\"\n",
+        doc.to_string(),
+    );
+
+    let mut options = toml_edit::ParseOptions::default();
+    options.lenient_numbers = true;
+    let doc = Document::parse_with_options("x = [0.5, 00.5e1]\n", options).unwrap();
+    assert_eq("x = [0.5, 0.5e1]\n", doc.to_string());
+
+    let err = Document::parse_lenient_numbers("x = 007\ny = 0_1\n").unwrap_err();
+    assert_eq(
+        "\
+TOML parse error at line 2, column 6
+  |
+2 | y = 0_1
+  |      ^
+expected newline, `#`
+",
+        err.to_string(),
+    );
+}
+
+#[test]
+fn document_stats() {
+    let doc = r#"
+title = "stats"
+
+[server]
+ports = [80, 443]
+
+[[worker]]
+limits = { cpu = 1 }
+
+[[worker]]
+"#
+    .parse::<Document>()
+    .unwrap();
+    assert_eq!(
+        doc.stats(),
+        toml_edit::DocumentStats {
+            tables: 3,
+            arrays_of_tables: 1,
+            values: 6,
+            keys: 6,
+            max_depth: 2,
+        }
+    );
+}
+
+#[test]
+fn round_trip_corpus() {
+    let corpus = [
+        "",
+        "\n\n# only a comment\n",
+        "\u{FEFF}[package]\nname = \"foo\"\n",
+        "a=1#no spaces\n  b   =   2   # spaced  \n",
+        "# comment\r\nkey = 'value'\r\n\r\n[table] # header\r\n",
+        r#"
+basic = """
+multi \
+  line\ttab é
+"""
+literal = '''
+raw \n ''quoted'' '''
+empty = """"""
+"#,
+        r#"nested = [ [ 1, 2 ], [ "a", 'b' ], [ [ ] ], ]
+multiline = [
+  # leading comment
+  1,   # one
+  [2, 3],
+  { x = 1 },  # inline
+  # trailing comment
+]
+"#,
+        r#"
+inline = {a=1,  b = { c = "d" } , 'e f' = [ ] }
+dotted.key = true
+"quoted.key" = +inf
+num = [ 0x_dead_beef, 0o755, 0b1_0, 1_000, -0.0, 6.626e-34, nan, -inf ]
+dt = [ 1979-05-27T07:32:00Z, 1979-05-27 07:32:00.999-08:00, 1979-05-27, 07:32:00 ]
+"#,
+        r#"
+[[fruit]]
+  name = "apple" # indented
+
+  [fruit.physical]
+    color = "red"
+
+[[fruit]] # second
+name = "banana"
+
+[ a . "b" . 'c' ]
+[x.y]
+"#,
+    ];
+    for input in corpus {
+        let doc = input.parse::<Document>().unwrap();
+        assert!(
+            doc.is_byte_identical_to(input),
+            "round trip changed {:?} into {:?}",
+            input,
+            doc.to_string()
+        );
+    }
+}
+
+#[test]
+fn remove_path() {
+    let mut doc = r#"
+[a.b]
+c = 1
+inline = { x = { y = 2 } }
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    let removed = doc.remove_path(&["a", "b", "c"]).unwrap();
+    assert_eq!(removed.as_integer(), Some(1));
+    let removed = doc.remove_path(&["a", "b", "inline", "x", "y"]).unwrap();
+    assert_eq!(removed.as_integer(), Some(2));
+    assert!(doc["a"]["b"]["inline"]["x"]
+        .as_inline_table()
+        .unwrap()
+        .is_empty());
+
+    doc.remove_path(&["a", "b", "inline"]).unwrap();
+    assert!(doc["a"]["b"].is_table());
+    assert_eq("\n[a.b]\n", doc.to_string());
+
+    assert!(doc.remove_path(&["a", "b", "c"]).is_none());
+    assert!(doc.remove_path(&["a", "missing", "c"]).is_none());
+    assert!(doc.remove_path(&[]).is_none());
+}
+
+#[test]
+fn prune_empty_tables() {
+    let mut doc = r#"title = "app"
+
+[server]
+port = 8080
+
+[cache]
+enabled = true
+
+[a.b.c]
+
+[[worker]]
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    doc.remove_path(&["cache", "enabled"]);
+    assert_eq(
+        r#"title = "app"
+
+[server]
+port = 8080
+
+[cache]
+
+[a.b.c]
+
+[[worker]]
+"#,
+        doc.to_string(),
+    );
+
+    doc.prune_empty_tables();
+    assert!(doc.get("cache").is_none());
+    assert!(doc.get("a").is_none());
+    assert_eq(
+        r#"title = "app"
+
+[server]
+port = 8080
+
+[[worker]]
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn bool_is_never_coerced() {
+    let doc = "flag = 1\noff = 0\non = true\n"
+        .parse::<Document>()
+        .unwrap();
+    assert!(doc["flag"].is_integer());
+    assert_eq!(doc["flag"].as_bool(), None);
+    assert_eq!(doc["off"].as_bool(), None);
+    assert_eq!(doc["on"].as_bool(), Some(true));
+
+    assert!("flag = True\n".parse::<Document>().is_err());
+    assert!("flag = yes\n".parse::<Document>().is_err());
+
+    assert_eq!(
+        Value::parse_bool("false").and_then(|v| v.as_bool()),
+        Some(false)
+    );
+    for invalid in ["1", "0", "True", "FALSE", " true", "yes", ""] {
+        assert!(Value::parse_bool(invalid).is_none(), "{invalid:?}");
+    }
+}
+
+#[test]
+fn multiline_string_line_continuation() {
+    let input = "str1 = \"\"\"\\\n       The quick brown \\\n\n\n       fox jumps over \\\n       the lazy dog.\"\"\"\nstr2 = \"\"\"\nnot \\   \n  trimmed \\\\\n\"\"\" # comment\n";
+    let doc = input.parse::<Document>().unwrap();
+    assert_eq!(
+        doc["str1"].as_str(),
+        Some("The quick brown fox jumps over the lazy dog.")
+    );
+    assert_eq!(doc["str2"].as_str(), Some("not trimmed \\\n"));
+    assert!(doc.is_byte_identical_to(input));
+}
+
+#[test]
+fn key_raw() {
+    let mut doc = "\"a b\" = 1\n'c'.d = 2\nplain = 3\n"
+        .parse::<Document>()
+        .unwrap();
+    let (key, _) = doc.get_key_value("a b").unwrap();
+    assert_eq!(key.raw(), Some("\"a b\""));
+    let (key, _) = doc.get_key_value("c").unwrap();
+    assert_eq!(key.raw(), Some("'c'"));
+    let (key, _) = doc.get_key_value("plain").unwrap();
+    assert_eq!(key.raw(), Some("plain"));
+    assert_eq!(Key::new("new").raw(), None);
+
+    assert!(doc.rename_key("a b", &Key::new("x y")));
+    assert!(doc.rename_key("plain", &"'kept'".parse::<Key>().unwrap()));
+    assert!(!doc.rename_key("missing", &Key::new("z")));
+    assert!(!doc.rename_key("c", &Key::new("kept")));
+    assert_eq("\"x y\" = 1\n'c'.d = 2\n'kept' = 3\n", doc.to_string());
+}
+
+#[test]
+fn array_of_tables_header_comments() {
+    let input = "# first\n[[a]]\nx=1\n# second\n[[a]]\nx=2\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    assert!(doc.is_byte_identical_to(input));
+
+    let a = doc["a"].as_array_of_tables_mut().unwrap();
+    let prefix = |decor: Option<&toml_edit::Decor>| {
+        decor
+            .and_then(|d| d.prefix())
+            .and_then(|p| p.as_str())
+            .map(str::to_owned)
+    };
+    assert_eq!(prefix(a.header_decor(0)), Some("# first\n".to_owned()));
+    assert_eq!(prefix(a.header_decor(1)), Some("# second\n".to_owned()));
+    assert!(a.header_decor(2).is_none());
+
+    a.remove(0);
+    a.header_decor_mut(0).unwrap().set_suffix(" # only");
+    assert_eq("# second\n[[a]] # only\nx=2\n", doc.to_string());
+}
+
+#[test]
+fn contains_path() {
+    let doc = r#"name = "app"
+[server]
+port = 8080
+tls = { cert = "a.pem" }
+[server.limits]
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    assert!(doc.contains_path(&["name"]));
+    assert!(doc.contains_path(&["server", "port"]));
+    assert!(doc.contains_path(&["server", "tls", "cert"]));
+    assert!(doc.contains_path(&["server", "limits"]));
+
+    assert!(!doc.contains_path(&["server", "host"]));
+    assert!(!doc.contains_path(&["server", "limits", "max"]));
+    assert!(!doc.contains_path(&["client", "port"]));
+
+    assert!(!doc.contains_path(&["name", "first"]));
+    assert!(!doc.contains_path(&["server", "port", "number"]));
+    assert!(!doc.contains_path(&[]));
+}
+
+#[test]
+fn set_array_wrap() {
+    let mut doc = r#"short = [ 1,2 ]
+long = ["alpha", "beta", "gamma", "delta"]
+nested = [[1, 2], ["a very long string", "that wraps"]]
+kept = [
+  1, # one
+  2,
+]
+"#
+    .parse::<Document>()
+    .unwrap();
+    doc.set_array_wrap(30);
+    assert_eq(
+        r#"short = [1, 2]
+long = [
+    "alpha",
+    "beta",
+    "gamma",
+    "delta",
+]
+nested = [
+    [1, 2],
+    [
+        "a very long string",
+        "that wraps",
+    ],
+]
+kept = [
+  1, # one
+  2,
+]
+"#,
+        doc.to_string(),
+    );
+}
+
+#[test]
+fn item_max_depth() {
+    let value = "{ a = { b = 1 } }".parse::<Value>().unwrap();
+    assert_eq!(Item::Value(value).max_depth(), 2);
+    assert_eq!(Item::Value(Value::from(1)).max_depth(), 0);
+    assert_eq!(Item::None.max_depth(), 0);
+
+    let doc = r#"a = 1
+b = [[1], []]
+[c]
+d = {}
+[[e]]
+f = [1]
+"#
+    .parse::<Document>()
+    .unwrap();
+    assert_eq!(doc["a"].max_depth(), 0);
+    assert_eq!(doc["b"].max_depth(), 2);
+    assert_eq!(doc["c"].max_depth(), 2);
+    assert_eq!(doc["e"].max_depth(), 2);
+    assert_eq!(doc.as_item().max_depth(), 3);
+}
+
+#[test]
+fn multiline_array_comments() {
+    let input = "a = [\n 1, # one\n 2, # two\n]\n";
+    let mut doc = input.parse::<Document>().unwrap();
+    assert!(doc.is_byte_identical_to(input));
+
+    // A comment after a comma is the prefix of the next element, or the array's trailing
+    let a = doc["a"].as_array_mut().unwrap();
+    let prefix = a.get(1).unwrap().decor().prefix().unwrap().as_str();
+    assert_eq!(prefix, Some(" # one\n "));
+    assert_eq!(a.trailing().as_str(), Some(" # two\n"));
+
+    a.replace(0, 10);
+    a.replace(1, 20);
+    assert_eq("a = [\n 10, # one\n 20, # two\n]\n", doc.to_string());
+}
+
+#[test]
+fn array_bracket_newline_style() {
+    for input in ["x = [\n1,\n]\n", "x = [1,\n]\n"] {
+        let doc = input.parse::<Document>().unwrap();
+        assert!(doc.is_byte_identical_to(input), "{input:?}");
+    }
+}
+
+#[test]
+fn set_value() {
+    let mut doc = Document::new();
+    doc.set_value(&["a", "b", "c"], 5);
+    assert_eq("[a.b]\nc = 5\n", doc.to_string());
+
+    let mut doc = "x = 1\n[a]\nlimits = { min = 1 }\n"
+        .parse::<Document>()
+        .unwrap();
+    doc.set_value(&["x"], "one");
+    doc.set_value(&["a", "limits", "min"], 2);
+    doc.set_value(&["a", "limits", "soft", "max"], 10);
+    assert_eq(
+        "x = \"one\"\n[a]\nlimits = { min = 2, soft = { max = 10 } }\n",
+        doc.to_string(),
+    );
+}
+
+#[test]
+#[should_panic = "cannot set `x.y`, `x` (integer) is not a table"]
+fn set_value_conflict() {
+    let mut doc = "x = 1\n".parse::<Document>().unwrap();
+    doc.set_value(&["x", "y"], 2);
+}
+
+#[test]
+fn array_iter_rev_nth() {
+    let doc = "a = [1, 2, 3, 4]\n".parse::<Document>().unwrap();
+    let a = doc["a"].as_array().unwrap();
+    let reversed = a
+        .iter()
+        .rev()
+        .filter_map(Value::as_integer)
+        .collect::<Vec<_>>();
+    assert_eq!(reversed, [4, 3, 2, 1]);
+    assert_eq!(a.iter().nth(1).and_then(Value::as_integer), Some(2));
+    assert_eq!(a.iter().rev().nth(1).and_then(Value::as_integer), Some(3));
+    assert_eq!(a.iter().skip(1).take(2).count(), 2);
+    assert_eq!(a.iter().len(), 4);
+    assert_eq!(a.iter().rev().skip(1).len(), 3);
+
+    let mut a = a.clone();
+    assert_eq!(a.iter_mut().rev().len(), 4);
+    let mut values = a.into_iter();
+    assert_eq!(values.next_back().and_then(|v| v.as_integer()), Some(4));
+    assert_eq!(values.len(), 3);
+}
+
+#[test]
+fn array_iter_skips_placeholders() {
+    let mut doc = "a = [1, 2, 3]\n".parse::<Document>().unwrap();
+    doc["a"][1] = toml_edit::Item::None;
+    let a = doc["a"].as_array().unwrap();
+    assert_eq!(a.iter().len(), 2);
+    let reversed = a
+        .iter()
+        .rev()
+        .filter_map(Value::as_integer)
+        .collect::<Vec<_>>();
+    assert_eq!(reversed, [3, 1]);
+    assert_eq!(a.clone().into_iter().len(), 2);
+}
+
+#[test]
+fn snapshot_outlives_edits() {
+    let mut doc = "[server]\nport = 80\n".parse::<Document>().unwrap();
+    let mut undo = vec![doc.snapshot()];
+
+    doc["server"]["port"] = toml_edit::value(8080);
+    undo.push(doc.snapshot());
+    doc["server"]["host"] = toml_edit::value("localhost");
+
+    let first = undo[0].clone();
+    assert_eq!(first["server"]["port"].as_integer(), Some(80));
+    assert!(!undo[1].contains_path(&["server", "host"]));
+    assert_eq("[server]\nport = 80\n", first.to_string());
+
+    doc = undo.pop().unwrap().to_document();
+    assert_eq("[server]\nport = 8080\n", doc.to_string());
+}
+
+#[test]
+fn key_validation() {
+    assert!(toml_edit::is_valid_bare_key("bare_key-1"));
+    let key = toml_edit::parse_key("bare_key-1").unwrap();
+    assert_eq!(key.get(), "bare_key-1");
+
+    assert!(!toml_edit::is_valid_bare_key("needs quotes"));
+    assert!(toml_edit::parse_key("needs quotes").is_err());
+    let key = toml_edit::parse_key("\"needs quotes\"").unwrap();
+    assert_eq!(key.get(), "needs quotes");
+
+    assert!(!toml_edit::is_valid_bare_key("a.b"));
+    let err = toml_edit::parse_key("a.b").unwrap_err();
+    assert_eq!(err.span(), Some(1..2));
+    let keys = Key::parse("a.b").unwrap();
+    assert_eq!(keys.len(), 2);
+}
+
+#[test]
+fn parse_with_options_blank_lines() {
+    let input = "a = 1
+
+
+
+# about b
+
+
+b = [
+    1,
+
+
+
+    2,
+]
+
+
+
+[t]
+c = \"\"\"
+x
+
+
+
+y\"\"\"
+
+
+
+";
+    let doc = Document::parse_with_options(input, toml_edit::ParseOptions::default()).unwrap();
+    assert_eq(input, doc.to_string());
+
+    let mut options = toml_edit::ParseOptions::default();
+    options.preserve_blank_lines = false;
+    let doc = Document::parse_with_options(input, options).unwrap();
+    let expected = "a = 1
+
+# about b
+
+b = [
+    1,
+
+    2,
+]
+
+[t]
+c = \"\"\"
+x
+
+
+
+y\"\"\"
+
+";
+    assert_eq(expected, doc.to_string());
+}
+
+#[test]
+fn parse_with_warnings() {
+    let input = r#"ints = [1, 2]
+mixed = [1, "two", 3.0]
+[t]
+nested = [[1], ["a", false]]
+"#;
+    let (doc, warnings) = Document::parse_with_warnings(input).unwrap();
+    assert_eq(input, doc.to_string());
+    let flagged = warnings
+        .iter()
+        .map(|w| (w.message(), &input[w.span().unwrap()]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        flagged,
+        [
+            (
+                "array mixes integer and string values, which TOML 0.5 rejects",
+                r#"[1, "two", 3.0]"#
+            ),
+            (
+                "array mixes string and boolean values, which TOML 0.5 rejects",
+                r#"["a", false]"#
+            ),
+        ]
+    );
+}
+
+#[test]
+fn apply_patch() {
+    let mut doc = r#"# app config
+[server]
+host = "localhost" # where to listen
+ports = [80, 443]
+
+[server.tls]
+enabled = false  # for now
+"#
+    .parse::<Document>()
+    .unwrap();
+    let patch = r#"server.tls.enabled = true
+server.ports = [8080]
+[log]
+level = "debug"
+"#
+    .parse::<Document>()
+    .unwrap();
+    doc.apply_patch(&patch);
+    let expected = r#"# app config
+[server]
+host = "localhost" # where to listen
+ports = [8080]
+
+[server.tls]
+enabled = true  # for now
+
+[log]
+level = "debug"
+"#;
+    assert_eq(expected, doc.to_string());
+}
+
+#[test]
+fn value_eq_primitives() {
+    let doc = r#"int = 0x05
+float = 5.0
+nan = nan
+bool = true
+string = 'five'
+"#
+    .parse::<Document>()
+    .unwrap();
+    let value = |key: &str| doc[key].as_value().unwrap();
+
+    assert!(*value("int") == 5);
+    assert!(*value("int") != 5.0);
+    assert!(*value("float") == 5.0);
+    assert!(*value("float") != 5);
+    assert!(*value("nan") == f64::NAN);
+    assert!(*value("bool") == true);
+    assert!(*value("bool") != false);
+    assert!(*value("string") == "five");
+    assert!(*value("string") == *"five");
+    assert!(*value("string") == "five".to_owned());
+    assert!(*value("string") != "5");
+    assert!(*value("int") != "5");
+}
+
+#[test]
+fn table_try_entry() {
+    let mut table = toml_edit::Table::new();
+    for key in ["", ".", "..", ".a", "a.", "a..b", " "] {
+        assert!(key.parse::<Key>().is_err(), "{key:?}");
+        assert!(Key::parse(key).is_err(), "{key:?}");
+        let err = table.try_entry(key).err().expect(key);
+        assert!(!err.to_string().is_empty());
+    }
+    assert!(table.is_empty());
+
+    for (i, key) in ["'.'", r#""""#, "plain"].into_iter().enumerate() {
+        let entry = table.try_entry(key).unwrap();
+        entry.or_insert(toml_edit::value(i as i64 + 1));
+    }
+    assert_eq!(table.to_string(), "'.' = 1\n\"\" = 2\nplain = 3\n");
+}
+
+#[test]
+fn parse_recover_unterminated_strings() {
+    let input = r#"title = "oops
+[server]
+host = 'localhost'
+"#;
+    let (doc, errors) = Document::parse_recover(input);
+    assert_eq!(errors.len(), 1);
+    assert!(!doc.contains_key("title"));
+    assert_eq!(doc["server"]["host"].as_str(), Some("localhost"));
+
+    let input = r#"[notes]
+text = """never closed
+still in the string
+
+[server]
+port = 8080
+"#;
+    let (doc, errors) = Document::parse_recover(input);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span(), Some(15..18));
+    assert!(doc["notes"].as_table().unwrap().is_empty());
+    assert_eq!(doc["server"]["port"].as_integer(), Some(8080));
+
+    let (doc, errors) = Document::parse_recover("a = 1\nb = 2\n");
+    assert!(errors.is_empty());
+    assert_eq!(doc.len(), 2);
+}
+
+#[test]
+fn parse_recover_crlf() {
+    let input = "a = 1\r\nb = [\r\n\t\r\n";
+    let (doc, errors) = Document::parse_recover(input);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(doc["a"].as_integer(), Some(1));
+    assert!(!doc.contains_key("b"));
+    assert_eq!(doc.line_ending(), toml_edit::LineEnding::Crlf);
+
+    let input = "a = \"oops\r\nb = 2\r\n";
+    let (doc, errors) = Document::parse_recover(input);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(doc["b"].as_integer(), Some(2));
+    assert_eq("         \r\nb = 2\r\n", doc.to_string());
+}
+
+#[test]
+fn all_key_paths() {
+    let doc = r#"title = "app"
+owner.name = "me"
+
+[server]
+"host name" = "localhost"
+ports = [80, 443]
+tls = { cert = "a.pem", "key.file" = "a.key" }
+
+[[server.backend]]
+url = "http://a"
+
+[[server.backend]]
+url = "http://b"
+"#
+    .parse::<Document>()
+    .unwrap();
+    assert_eq!(
+        doc.all_key_paths(),
+        [
+            "title",
+            "owner",
+            "owner.name",
+            "server",
+            "server.\"host name\"",
+            "server.ports",
+            "server.tls",
+            "server.tls.cert",
+            "server.tls.\"key.file\"",
+            "server.backend[0]",
+            "server.backend[0].url",
+            "server.backend[1]",
+            "server.backend[1].url",
+        ]
+    );
+}
+
+#[test]
+fn array_of_tables_representations() {
+    let doc = r#"
+inline = [{ a = 1 }, { a = 2 }]
+mixed = [{ a = 1 }, 2]
+empty = []
+numbers = [1, 2]
+
+[[header]]
+a = 1
+
+[[header]]
+a = 2
+"#
+    .parse::<Document>()
+    .unwrap();
+
+    assert!(doc["header"].is_array_of_tables());
+    assert!(doc["header"]
+        .as_value()
+        .map_or(true, |v| !v.is_array_of_inline_tables()));
+
+    assert!(!doc["inline"].is_array_of_tables());
+    assert!(doc["inline"]
+        .as_value()
+        .unwrap()
+        .is_array_of_inline_tables());
+
+    for key in ["mixed", "empty", "numbers"] {
+        assert!(!doc[key].is_array_of_tables(), "{key}");
+        assert!(
+            !doc[key].as_value().unwrap().is_array_of_inline_tables(),
+            "{key}"
+        );
+    }
+}
+
+#[test]
+fn document_rendered_len() {
+    let inputs = [
+        "",
+        "a = 1",
+        "a = 1\n",
+        "\u{FEFF}title = \"ünïcode\"\r\n[t]\r\nb = [1,\r\n  2]\r\n",
+        r#"
+# leading comment
+a = 1
+[t]
+b = { c = "d" }
+
+[[aot]]
+e = 'f' # trailing
+
+"#,
+    ];
+    for input in inputs {
+        let doc = input.parse::<Document>().unwrap();
+        let rendered = doc.to_string();
+        assert_eq!(doc.rendered_len(), rendered.len(), "{input:?}");
+        assert_eq!(doc.line_count(), rendered.lines().count(), "{input:?}");
+    }
+
+    let mut doc = "a = 1\n".parse::<Document>().unwrap();
+    doc["t"] = toml_edit::table();
+    doc["t"]["b"] = toml_edit::value("x");
+    assert_eq!(doc.rendered_len(), doc.to_string().len());
+    assert_eq!(doc.line_count(), 4);
+}
@@ -0,0 +1,71 @@
+use snapbox::assert_eq;
+use toml_edit::{table, value, Document, DocumentWriter, Item, WriteError};
+
+#[test]
+fn streamed_matches_rendered() {
+    let mut writer = DocumentWriter::new(String::new());
+    writer.push(&["title"], "streamed").unwrap();
+    writer.push(&["version"], 2).unwrap();
+    writer.push(&["server", "host"], "localhost").unwrap();
+    writer
+        .push(&["server", "ports"], toml_edit::Array::from_iter([80, 443]))
+        .unwrap();
+    writer.push(&["a", "b.c", "enabled"], true).unwrap();
+    writer.push(&["a", "b.c", "ratio"], 0.5).unwrap();
+    let streamed = writer.finish();
+
+    let mut doc = Document::new();
+    doc["title"] = value("streamed");
+    doc["version"] = value(2);
+    doc["server"] = table();
+    doc["server"]["host"] = value("localhost");
+    doc["server"]["ports"] = value(toml_edit::Array::from_iter([80, 443]));
+    let mut a = toml_edit::Table::new();
+    a.set_implicit(true);
+    doc["a"] = Item::Table(a);
+    doc["a"]["b.c"] = table();
+    doc["a"]["b.c"]["enabled"] = value(true);
+    doc["a"]["b.c"]["ratio"] = value(0.5);
+
+    assert_eq(doc.to_string(), streamed);
+}
+
+#[test]
+fn reopening_table_errors() {
+    let mut writer = DocumentWriter::new(String::new());
+    writer.push(&["server", "host"], "localhost").unwrap();
+    writer.push(&["client", "host"], "localhost").unwrap();
+    assert_eq!(
+        writer.push(&["server", "port"], 80),
+        Err(WriteError::Reopened(vec!["server".to_owned()]))
+    );
+    assert_eq!(
+        writer.push(&["title"], "late"),
+        Err(WriteError::Reopened(vec![]))
+    );
+    assert_eq(
+        "[server]\nhost = \"localhost\"\n\n[client]\nhost = \"localhost\"\n",
+        writer.finish(),
+    );
+}
+
+#[test]
+fn conflicting_keys_error() {
+    let mut writer = DocumentWriter::new(String::new());
+    writer.push(&["a"], 1).unwrap();
+    assert_eq!(
+        writer.push(&["a"], 2),
+        Err(WriteError::Duplicate(vec!["a".to_owned()]))
+    );
+    assert_eq!(
+        writer.push(&["a", "b", "c"], 3),
+        Err(WriteError::Duplicate(vec!["a".to_owned()]))
+    );
+    writer.push(&["x", "y", "z"], 4).unwrap();
+    writer.push(&["x", "w"], 5).unwrap();
+    assert_eq!(
+        writer.push(&["x", "y"], 6),
+        Err(WriteError::Duplicate(vec!["x".to_owned(), "y".to_owned()]))
+    );
+    assert_eq("a = 1\n\n[x.y]\nz = 4\n\n[x]\nw = 5\n", writer.finish());
+}